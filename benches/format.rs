@@ -0,0 +1,107 @@
+//! Measures the formatting paths and counts their heap allocations.
+//!
+//! Run with `cargo bench`. The allocation-free paths panic if they ever allocate.
+
+use std::alloc::{GlobalAlloc, Layout, System as SystemAlloc};
+use std::fmt::{self, Write};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use bittenhumans::consts::{Magnitude, System};
+use bittenhumans::display::HumanBytes;
+use bittenhumans::ByteSizeFormatter;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        SystemAlloc.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        SystemAlloc.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// A fixed-size buffer on the stack, standing in for a status bar's line buffer.
+struct StackBuffer {
+    bytes: [u8; 64],
+    len: usize,
+}
+
+impl Write for StackBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.bytes
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+const ITERATIONS: u64 = 1_000_000;
+
+/// Runs `f` for every iteration and reports the time and allocations per call.
+fn bench(name: &str, allocation_free: bool, mut f: impl FnMut(u64)) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for i in 0..ITERATIONS {
+        f(black_box(i * 7919));
+    }
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+
+    println!(
+        "{name:<32} {:>8.1} ns/iter {:>6.2} allocations/iter",
+        elapsed.as_nanos() as f64 / ITERATIONS as f64,
+        allocations as f64 / ITERATIONS as f64
+    );
+    if allocation_free {
+        assert_eq!(0, allocations, "{name} allocated");
+    }
+}
+
+fn main() {
+    let formatter = ByteSizeFormatter::new(System::Binary, Magnitude::Mega);
+    let mut buffer = StackBuffer {
+        bytes: [0; 64],
+        len: 0,
+    };
+    let mut string = String::with_capacity(64);
+
+    bench("format_value", false, |value| {
+        black_box(formatter.format_value(value));
+    });
+    bench("format_auto", false, |value| {
+        black_box(ByteSizeFormatter::format_auto(value, System::Binary));
+    });
+    bench("write_to (stack buffer)", true, |value| {
+        buffer.len = 0;
+        formatter.write_to(&mut buffer, value).unwrap();
+        black_box(&buffer.bytes[..buffer.len]);
+    });
+    bench("display (reused String)", true, |value| {
+        string.clear();
+        write!(string, "{}", formatter.display(value)).unwrap();
+        black_box(&string);
+    });
+    bench("HumanBytes (reused String)", true, |value| {
+        string.clear();
+        write!(string, "{}", HumanBytes(value, System::Binary)).unwrap();
+        black_box(&string);
+    });
+    bench("HumanBytes padded (reused String)", true, |value| {
+        string.clear();
+        write!(string, "{:>10.1}", HumanBytes(value, System::Decimal)).unwrap();
+        black_box(&string);
+    });
+}
@@ -1,21 +1,107 @@
 use enum_iterator::Sequence;
 
-pub const MAGNITUDE_PREFIXES: [&str; 6] = ["K", "M", "G", "T", "P", "E"];
+pub const MAGNITUDE_PREFIXES: [&str; 10] = ["K", "M", "G", "T", "P", "E", "Z", "Y", "R", "Q"];
 
 #[repr(u8)]
-#[derive(Debug, PartialEq, Sequence, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Sequence, Clone, Copy)]
 pub enum Magnitude {
-    Kilo = 1,
+    /// Plain bytes without a prefix.
+    Byte = 0,
+    Kilo,
     Mega,
     Giga,
     Tera,
     Peta,
     Exa,
+    /// The first magnitude whose divisor does not fit into a `u64`, so
+    /// [`get_divisor`](crate::ByteSizeFormatter::get_divisor) panics from here on. Use
+    /// [`checked_divisor`](crate::ByteSizeFormatter::checked_divisor) instead.
+    Zetta,
+    Yotta,
+    Ronna,
+    Quetta,
 }
 
+impl Magnitude {
+    /// The magnitude whose divisor is the base raised to `exponent`, if there is one.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::consts::Magnitude;
+    ///
+    /// assert_eq!(Some(Magnitude::Giga), Magnitude::from_exponent(3));
+    /// assert_eq!(None, Magnitude::from_exponent(11));
+    /// ```
+    pub const fn from_exponent(exponent: u8) -> Option<Self> {
+        Some(match exponent {
+            0 => Magnitude::Byte,
+            1 => Magnitude::Kilo,
+            2 => Magnitude::Mega,
+            3 => Magnitude::Giga,
+            4 => Magnitude::Tera,
+            5 => Magnitude::Peta,
+            6 => Magnitude::Exa,
+            7 => Magnitude::Zetta,
+            8 => Magnitude::Yotta,
+            9 => Magnitude::Ronna,
+            10 => Magnitude::Quetta,
+            _ => return None,
+        })
+    }
+
+    /// The power the system base is raised to for this magnitude.
+    pub const fn exponent(&self) -> u8 {
+        *self as u8
+    }
+}
+
+/// A numeral system, deciding both the base of each magnitude and how units are labelled.
+///
+/// Use [`base`](System::base) for the factor between magnitudes. Casting with `as` still yields
+/// 1000 and 1024 for [`Decimal`](System::Decimal) and [`Binary`](System::Binary), but not for
+/// [`Jedec`](System::Jedec), whose discriminant is only a tag.
+///
+/// The enum is non-exhaustive since [`Jedec`](System::Jedec) was added, so matches outside this
+/// crate need a wildcard arm.
 #[repr(u16)]
-#[derive(Debug, PartialEq, Sequence, Clone, Copy)]
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq, Hash, Sequence, Clone, Copy)]
 pub enum System {
+    /// Powers of 1000, labelled KB, MB, GB, ...
     Decimal = 1000,
+    /// Powers of 1024, labelled with the IEC infix: KiB, MiB, GiB, ...
     Binary = 1024,
+    /// Powers of 1024, labelled KB, MB, GB, ... as in JEDEC memory standards, Windows and many
+    /// legacy tools.
+    ///
+    /// These labels are identical to the [`Decimal`](System::Decimal) ones, so a string like
+    /// "1 MB" is ambiguous without knowing which system produced it. Prefer
+    /// [`Binary`](System::Binary) unless the output has to match such software.
+    Jedec = 1,
+}
+
+impl System {
+    /// The factor between two adjacent magnitudes.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::consts::System;
+    ///
+    /// assert_eq!(1024, System::Jedec.base());
+    /// assert_eq!(1024, System::Binary as u64);
+    /// ```
+    pub const fn base(&self) -> u16 {
+        match self {
+            System::Decimal => 1000,
+            System::Binary | System::Jedec => 1024,
+        }
+    }
+
+    /// The infix between prefix and unit, `"i"` for binary units and empty otherwise.
+    pub const fn infix(&self) -> &'static str {
+        match self {
+            System::Binary => "i",
+            System::Decimal | System::Jedec => "",
+        }
+    }
 }
@@ -0,0 +1,305 @@
+use core::fmt;
+
+use crate::consts::{Magnitude, System};
+use crate::locale::Locale;
+use crate::options::{RoundingMode, Units};
+use crate::ByteSizeFormatter;
+
+/// A unit of data, such as MiB or GB: a magnitude in a numeral system.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Unit {
+    system: System,
+    magnitude: Magnitude,
+}
+
+impl Unit {
+    /// Plain bytes, which are the same in every system.
+    pub const BYTE: Unit = Unit::new(System::Decimal, Magnitude::Byte);
+
+    pub const fn new(system: System, magnitude: Magnitude) -> Self {
+        Self { system, magnitude }
+    }
+
+    pub const fn get_system(&self) -> System {
+        self.system
+    }
+
+    pub const fn get_magnitude(&self) -> Magnitude {
+        self.magnitude
+    }
+
+    /// The number of bytes in one of this unit.
+    pub const fn divisor(&self) -> u128 {
+        ByteSizeFormatter::compute_divisor(self.system, self.magnitude)
+    }
+}
+
+/// Shows the unit label, e.g. `"MiB"`.
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(Locale::EN.symbol(self.system, self.magnitude, Units::Bytes))
+    }
+}
+
+/// A non-negative rational number in lowest terms, such as the exact result of a unit
+/// conversion.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Ratio {
+    numerator: u128,
+    denominator: u128,
+}
+
+const fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Divides `numerator` by `denominator`, rounding the quotient to an integer.
+const fn div_rounded(numerator: u128, denominator: u128, rounding: RoundingMode) -> u128 {
+    let (quotient, remainder) = (numerator / denominator, numerator % denominator);
+    // Compared as `remainder` against `denominator - remainder` to avoid overflowing.
+    let round_up = match rounding {
+        RoundingMode::Floor => false,
+        RoundingMode::Ceil => remainder != 0,
+        RoundingMode::HalfEven => {
+            remainder > denominator - remainder
+                || (remainder == denominator - remainder && quotient % 2 == 1)
+        }
+        RoundingMode::HalfUp => remainder != 0 && remainder >= denominator - remainder,
+    };
+    quotient + round_up as u128
+}
+
+impl Ratio {
+    /// Creates the ratio `numerator / denominator` in lowest terms.
+    ///
+    /// # Panics
+    ///
+    /// If `denominator` is zero.
+    pub const fn new(numerator: u128, denominator: u128) -> Self {
+        assert!(denominator != 0, "ratio with a zero denominator");
+        let divisor = gcd(numerator, denominator);
+        Self {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        }
+    }
+
+    pub const fn integer(value: u128) -> Self {
+        Self {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+
+    pub const fn numerator(&self) -> u128 {
+        self.numerator
+    }
+
+    pub const fn denominator(&self) -> u128 {
+        self.denominator
+    }
+
+    pub const fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+
+    /// Rounds the ratio to an integer.
+    pub const fn round(&self, rounding: RoundingMode) -> u128 {
+        div_rounded(self.numerator, self.denominator, rounding)
+    }
+
+    /// Rounds the ratio to `decimals` decimal places, or returns `None` if the result does not
+    /// fit.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::convert::Ratio;
+    /// use bittenhumans::options::RoundingMode;
+    ///
+    /// let third = Ratio::new(1, 3);
+    /// assert_eq!(Some(Ratio::new(333, 1000)), third.round_to(3, RoundingMode::HalfEven));
+    /// assert_eq!(Some(Ratio::new(334, 1000)), third.round_to(3, RoundingMode::Ceil));
+    /// ```
+    pub const fn round_to(&self, decimals: u32, rounding: RoundingMode) -> Option<Self> {
+        let Some(scale) = 10_u128.checked_pow(decimals) else {
+            return None;
+        };
+        // Only the part of the scale not already in the denominator has to be multiplied in.
+        let common = gcd(scale, self.denominator);
+        let Some(numerator) = self.numerator.checked_mul(scale / common) else {
+            return None;
+        };
+        let scaled = div_rounded(numerator, self.denominator / common, rounding);
+        Some(Self::new(scaled, scale))
+    }
+
+    /// The nearest `f64` to the ratio.
+    pub fn to_f64(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+}
+
+/// Shows the ratio as an integer or a fraction, e.g. `"3"` or `"3/2"`.
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.denominator {
+            1 => write!(f, "{}", self.numerator),
+            denominator => write!(f, "{}/{}", self.numerator, denominator),
+        }
+    }
+}
+
+/// Converts a quantity from one unit to another exactly, or returns `None` if the result does not
+/// fit into a [`Ratio`].
+///
+/// # Arguments
+///
+/// * `quantity` - The amount of `from` units, e.g. `Ratio::new(3, 2)` for 1.5
+/// * `from` - The unit the quantity is expressed in
+/// * `to` - The unit to convert to
+///
+/// # Example
+/// ```
+/// use bittenhumans::consts::{Magnitude, System};
+/// use bittenhumans::convert::{convert, Ratio, Unit};
+/// use bittenhumans::options::RoundingMode;
+///
+/// // How many MB is 1.5 GiB?
+/// let gib = Unit::new(System::Binary, Magnitude::Giga);
+/// let mb = Unit::new(System::Decimal, Magnitude::Mega);
+/// let converted = convert(Ratio::new(3, 2), gib, mb).unwrap();
+/// assert_eq!(Ratio::new(1_610_612_736, 1_000_000), converted);
+/// assert_eq!(1611, converted.round(RoundingMode::HalfEven));
+/// assert_eq!(
+///     Some(Ratio::new(161_061, 100)),
+///     converted.round_to(2, RoundingMode::HalfEven)
+/// );
+///
+/// // Vendors' "terabytes" as the TiB an OS shows
+/// let tb = Unit::new(System::Decimal, Magnitude::Tera);
+/// let tib = Unit::new(System::Binary, Magnitude::Tera);
+/// let converted = convert(Ratio::integer(4), tb, tib).unwrap();
+/// assert_eq!(Some(Ratio::new(363, 100)), converted.round_to(2, RoundingMode::Floor));
+/// ```
+///
+/// # Returns
+///
+/// The quantity in `to` units, in lowest terms
+pub const fn convert(quantity: Ratio, from: Unit, to: Unit) -> Option<Ratio> {
+    // Cross-cancelling before multiplying keeps the intermediate values as small as possible.
+    let (mut numerator, mut denominator) = (quantity.numerator, quantity.denominator);
+    let (mut from, mut to) = (from.divisor(), to.divisor());
+    let common = gcd(from, to);
+    (from, to) = (from / common, to / common);
+    let common = gcd(numerator, to);
+    (numerator, to) = (numerator / common, to / common);
+    let common = gcd(from, denominator);
+    (from, denominator) = (from / common, denominator / common);
+
+    match (numerator.checked_mul(from), denominator.checked_mul(to)) {
+        (Some(numerator), Some(denominator)) => Some(Ratio {
+            numerator,
+            denominator,
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ratio() {
+        assert_eq!(Ratio::integer(3), Ratio::new(6, 2));
+        assert_eq!(
+            (3, 2),
+            (Ratio::new(9, 6).numerator(), Ratio::new(9, 6).denominator())
+        );
+        assert_eq!(Ratio::integer(0), Ratio::new(0, 7));
+        assert_eq!("3/2", Ratio::new(3, 2).to_string());
+        assert_eq!("4", Ratio::new(8, 2).to_string());
+        assert_eq!(1.5, Ratio::new(3, 2).to_f64());
+
+        let round = |n, d, mode| Ratio::new(n, d).round(mode);
+        assert_eq!(1, round(3, 2, RoundingMode::Floor));
+        assert_eq!(2, round(3, 2, RoundingMode::Ceil));
+        assert_eq!(2, round(3, 2, RoundingMode::HalfEven));
+        assert_eq!(2, round(5, 2, RoundingMode::HalfEven));
+        assert_eq!(3, round(5, 2, RoundingMode::HalfUp));
+        assert_eq!(0, round(1, 3, RoundingMode::HalfUp));
+        assert_eq!(u128::MAX, round(u128::MAX, 1, RoundingMode::Ceil));
+        assert_eq!(
+            u128::MAX / 2 + 1,
+            round(u128::MAX, 2, RoundingMode::HalfEven)
+        );
+
+        assert_eq!(
+            Some(Ratio::new(5, 2)),
+            Ratio::new(5, 2).round_to(3, RoundingMode::Floor)
+        );
+        assert_eq!(
+            Some(Ratio::integer(1)),
+            Ratio::new(2, 3).round_to(0, RoundingMode::HalfUp)
+        );
+        assert_eq!(None, Ratio::new(1, 3).round_to(39, RoundingMode::Floor));
+        assert_eq!(
+            None,
+            Ratio::integer(u128::MAX).round_to(1, RoundingMode::Floor)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ratio with a zero denominator")]
+    fn zero_denominator() {
+        Ratio::new(1, 0);
+    }
+
+    #[test]
+    fn conversions() {
+        let unit = Unit::new;
+        let kib = unit(System::Binary, Magnitude::Kilo);
+        let kb = unit(System::Decimal, Magnitude::Kilo);
+        assert_eq!("KiB", kib.to_string());
+        assert_eq!("B", Unit::BYTE.to_string());
+        assert_eq!(1024, kib.divisor());
+
+        assert_eq!(
+            Some(Ratio::integer(1024)),
+            convert(Ratio::integer(1), kib, Unit::BYTE)
+        );
+        assert_eq!(
+            Some(Ratio::new(128, 125)),
+            convert(Ratio::integer(1), kib, kb)
+        );
+        assert_eq!(
+            Some(Ratio::new(125, 128)),
+            convert(Ratio::integer(1), kb, kib)
+        );
+        assert_eq!(
+            Some(Ratio::integer(1)),
+            convert(Ratio::integer(1), kb, unit(System::Jedec, Magnitude::Kilo))
+                .map(|ratio| ratio.round_to(0, RoundingMode::HalfEven).unwrap())
+        );
+
+        // Conversions between the largest units only succeed thanks to cancelling.
+        let qib = unit(System::Binary, Magnitude::Quetta);
+        let qb = unit(System::Decimal, Magnitude::Quetta);
+        assert_eq!(
+            Some(Ratio::integer(1 << 100)),
+            convert(Ratio::integer(1), qib, Unit::BYTE)
+        );
+        assert_eq!(
+            Some(Ratio::new(1 << 70, 5_u128.pow(30))),
+            convert(Ratio::integer(1), qib, qb)
+        );
+        assert_eq!(None, convert(Ratio::integer(1 << 30), qib, Unit::BYTE));
+        assert_eq!(
+            Some(Ratio::new(3, 1 << 100)),
+            convert(Ratio::integer(3), Unit::BYTE, qib)
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use core::fmt::{self, Write};
+
+use crate::consts::System;
+use crate::options::FormatOptions;
+use crate::ByteSizeFormatter;
+
+/// Counts the characters written to it, to measure output before padding it.
+struct CharCount(usize);
+
+impl fmt::Write for CharCount {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+/// Writes a formatted value padded to the width of `f`, using its fill character and alignment.
+/// Values are left-aligned unless requested otherwise, like strings.
+fn write_padded(
+    f: &mut fmt::Formatter<'_>,
+    formatter: &ByteSizeFormatter,
+    value: u64,
+) -> fmt::Result {
+    let Some(width) = f.width() else {
+        return formatter.write_to(f, value);
+    };
+
+    let mut len = CharCount(0);
+    formatter.write_to(&mut len, value)?;
+    let padding = width.saturating_sub(len.0);
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Right) => (padding, 0),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        _ => (0, padding),
+    };
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    formatter.write_to(f, value)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+/// Overrides the number of decimals in `options` with the precision of `f`, if given.
+fn with_precision(options: FormatOptions, f: &fmt::Formatter<'_>) -> FormatOptions {
+    match f.precision() {
+        Some(precision) => options.precision(precision.min(u8::MAX as usize) as u8),
+        None => options,
+    }
+}
+
+/// Formats a value with a borrowed formatter when displayed, without allocating. Returned by
+/// [`ByteSizeFormatter::display`].
+///
+/// The width, fill and alignment flags pad the whole `"value unit"` string, and a precision
+/// overrides the number of decimals.
+///
+/// # Example
+/// ```
+/// use bittenhumans::ByteSizeFormatter;
+/// use bittenhumans::consts::{Magnitude, System};
+///
+/// let formatter = ByteSizeFormatter::new(System::Binary, Magnitude::Mega);
+/// assert_eq!("[  1.4 MiB]", format!("[{:>9.1}]", formatter.display(1_500_000)));
+/// ```
+#[derive(Clone, Copy)]
+pub struct SizeDisplay<'a> {
+    formatter: &'a ByteSizeFormatter,
+    value: u64,
+}
+
+impl<'a> SizeDisplay<'a> {
+    pub(crate) fn new(formatter: &'a ByteSizeFormatter, value: u64) -> Self {
+        Self { formatter, value }
+    }
+}
+
+impl fmt::Display for SizeDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.precision() {
+            None => write_padded(f, self.formatter, self.value),
+            Some(_) => {
+                let options = with_precision(*self.formatter.get_options(), f);
+                let formatter = ByteSizeFormatter::new(
+                    self.formatter.get_system(),
+                    self.formatter.get_magnitude(),
+                )
+                .with_options(options);
+                write_padded(f, &formatter, self.value)
+            }
+        }
+    }
+}
+
+/// A byte count displayed in the magnitude that fits it, like
+/// [`ByteSizeFormatter::format_auto`] but without allocating.
+///
+/// The standard formatting flags are honoured: width, fill and alignment pad the whole
+/// `"value unit"` string, a precision sets the number of decimals, and the alternate flag `#`
+/// switches to the [`Binary`](System::Binary) system.
+///
+/// # Example
+/// ```
+/// use bittenhumans::consts::System;
+/// use bittenhumans::display::HumanBytes;
+///
+/// assert_eq!("1.43 MiB", HumanBytes(1_500_000, System::Binary).to_string());
+/// assert_eq!("used 512 B", format!("used {}", HumanBytes(512, System::Decimal)));
+///
+/// let size = HumanBytes(1_500_000, System::Decimal);
+/// assert_eq!("    1.5 MB", format!("{size:>10.1}"));
+/// assert_eq!("1.43 MiB", format!("{size:#}"));
+/// assert_eq!("1.50 MB___", format!("{size:_<10}"));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct HumanBytes(pub u64, pub System);
+
+impl fmt::Display for HumanBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let system = match f.alternate() {
+            true => System::Binary,
+            false => self.1,
+        };
+        let options = with_precision(FormatOptions::new(), f);
+        write_padded(
+            f,
+            &ByteSizeFormatter::fit_with(self.0, system, options),
+            self.0,
+        )
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::consts::Magnitude;
+    use crate::options::{BitSymbol, FormatOptions, Units};
+
+    #[test]
+    fn display() {
+        let bits = FormatOptions::new().units(Units::Bits(BitSymbol::Long));
+        let formatter = ByteSizeFormatter::new(System::Binary, Magnitude::Kilo).with_options(bits);
+        assert_eq!("8.00 Kibit", formatter.display(1024).to_string());
+        assert_eq!(
+            formatter.format_value(12345),
+            formatter.display(12345).to_string()
+        );
+
+        for value in [0, 1023, 1024, 999_999, 1_000_000, u64::MAX] {
+            for system in enum_iterator::all::<System>() {
+                assert_eq!(
+                    ByteSizeFormatter::format_auto(value, system),
+                    HumanBytes(value, system).to_string()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn flags() {
+        let size = HumanBytes(1_500_000, System::Decimal);
+        assert_eq!("    1.5 MB", format!("{size:>10.1}"));
+        assert_eq!("  1.5 MB  ", format!("{size:^10.1}"));
+        assert_eq!(" 1.50 MB  ", format!("{size:^10}"));
+        assert_eq!("1.50 MB   ", format!("{size:10}"));
+        assert_eq!("1.50 MB", format!("{size:3}"));
+        assert_eq!("1.500 MB", format!("{size:.3}"));
+        assert_eq!("**1.4 MiB", format!("{size:*>#9.1}"));
+        assert_eq!(
+            "2 MB",
+            format!("{:.0}", HumanBytes(1_500_000, System::Decimal))
+        );
+        // Rounding to the requested precision may move the value to the next magnitude.
+        assert_eq!(
+            "1 MiB",
+            format!("{:#.0}", HumanBytes(1_048_500, System::Decimal))
+        );
+        assert_eq!(
+            "1023 B",
+            format!("{:#.1}", HumanBytes(1023, System::Decimal))
+        );
+
+        let formatter = ByteSizeFormatter::new(System::Binary, Magnitude::Kilo);
+        assert_eq!("[1.5 KiB  ]", format!("[{:9.1}]", formatter.display(1536)));
+        assert_eq!("[ 1.50 KiB]", format!("[{:>9}]", formatter.display(1536)));
+    }
+}
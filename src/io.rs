@@ -0,0 +1,265 @@
+use std::fmt;
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
+use std::time::{Duration, Instant};
+
+use crate::consts::System;
+use crate::options::FormatOptions;
+use crate::rate::RateFormatter;
+use crate::ByteSizeFormatter;
+
+/// The byte count and start time shared by the counting adapters.
+#[derive(Debug, Clone, Copy)]
+struct Stats {
+    bytes: u64,
+    start: Instant,
+    system: System,
+}
+
+impl Stats {
+    fn new(system: System) -> Self {
+        Self {
+            bytes: 0,
+            start: Instant::now(),
+            system,
+        }
+    }
+
+    fn add(&mut self, bytes: usize) {
+        self.bytes = self.bytes.saturating_add(bytes as u64);
+    }
+
+    fn rate(&self) -> f64 {
+        self.bytes as f64 / self.start.elapsed().as_secs_f64()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rate = RateFormatter::new(self.system)
+            .with_options(FormatOptions::new().significant_figures(3));
+        write!(
+            f,
+            "{} at {}",
+            ByteSizeFormatter::format_auto(self.bytes, self.system),
+            rate.format(self.bytes, self.start.elapsed())
+        )
+    }
+}
+
+/// A [`Read`] adapter counting the bytes read through it since it was created.
+///
+/// [`BufRead`] and [`Seek`] are passed through; seeking does not change the count. The
+/// [`Display`](fmt::Display) implementation shows the total and the average rate, e.g.
+/// `"1.43 MiB at 20.1 MiB/s"`.
+///
+/// # Example
+/// ```
+/// use std::io;
+///
+/// use bittenhumans::consts::System;
+/// use bittenhumans::io::CountingReader;
+///
+/// let mut reader = CountingReader::new(&[0u8; 1_500_000][..], System::Binary);
+/// io::copy(&mut reader, &mut io::sink()).unwrap();
+/// assert_eq!(1_500_000, reader.total());
+/// assert!(format!("copied {reader}").starts_with("copied 1.43 MiB at "));
+/// ```
+#[derive(Debug)]
+pub struct CountingReader<R> {
+    inner: R,
+    stats: Stats,
+}
+
+impl<R> CountingReader<R> {
+    /// Wraps `inner`, formatting the statistics in the given numeral system.
+    pub fn new(inner: R, system: System) -> Self {
+        Self {
+            inner,
+            stats: Stats::new(system),
+        }
+    }
+
+    /// The number of bytes read so far.
+    pub fn total(&self) -> u64 {
+        self.stats.bytes
+    }
+
+    /// The time since the adapter was created.
+    pub fn elapsed(&self) -> Duration {
+        self.stats.start.elapsed()
+    }
+
+    /// The average rate in bytes per second since the adapter was created.
+    pub fn rate(&self) -> f64 {
+        self.stats.rate()
+    }
+
+    pub fn get_system(&self) -> System {
+        self.stats.system
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.stats.add(read);
+        Ok(read)
+    }
+}
+
+impl<R: BufRead> BufRead for CountingReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.stats.add(amount);
+        self.inner.consume(amount);
+    }
+}
+
+impl<R: Seek> Seek for CountingReader<R> {
+    fn seek(&mut self, position: SeekFrom) -> io::Result<u64> {
+        self.inner.seek(position)
+    }
+}
+
+impl<R> fmt::Display for CountingReader<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.stats.fmt(f)
+    }
+}
+
+/// A [`Write`] adapter counting the bytes written through it since it was created.
+///
+/// [`Seek`] is passed through and does not change the count. The [`Display`](fmt::Display)
+/// implementation shows the total and the average rate, e.g. `"1.43 MiB at 20.1 MiB/s"`.
+///
+/// # Example
+/// ```
+/// use std::io::{self, Write};
+///
+/// use bittenhumans::consts::System;
+/// use bittenhumans::io::CountingWriter;
+///
+/// let mut writer = CountingWriter::new(Vec::new(), System::Decimal);
+/// writer.write_all(b"hello").unwrap();
+/// assert_eq!(5, writer.total());
+/// assert!(writer.to_string().starts_with("5 B at "));
+/// ```
+#[derive(Debug)]
+pub struct CountingWriter<W> {
+    inner: W,
+    stats: Stats,
+}
+
+impl<W> CountingWriter<W> {
+    /// Wraps `inner`, formatting the statistics in the given numeral system.
+    pub fn new(inner: W, system: System) -> Self {
+        Self {
+            inner,
+            stats: Stats::new(system),
+        }
+    }
+
+    /// The number of bytes written so far.
+    pub fn total(&self) -> u64 {
+        self.stats.bytes
+    }
+
+    /// The time since the adapter was created.
+    pub fn elapsed(&self) -> Duration {
+        self.stats.start.elapsed()
+    }
+
+    /// The average rate in bytes per second since the adapter was created.
+    pub fn rate(&self) -> f64 {
+        self.stats.rate()
+    }
+
+    pub fn get_system(&self) -> System {
+        self.stats.system
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.stats.add(written);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Seek> Seek for CountingWriter<W> {
+    fn seek(&mut self, position: SeekFrom) -> io::Result<u64> {
+        self.inner.seek(position)
+    }
+}
+
+impl<W> fmt::Display for CountingWriter<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.stats.fmt(f)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reader() {
+        let data = b"first line\nsecond line\n".to_vec();
+        let mut reader = CountingReader::new(io::Cursor::new(data), System::Binary);
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(11, reader.total());
+
+        reader.seek(SeekFrom::Start(0)).unwrap();
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).unwrap();
+        assert_eq!(34, reader.total());
+        assert!(reader.rate() > 0.0);
+        assert!(reader.to_string().starts_with("34 B at "));
+        assert!(reader.to_string().ends_with("/s"));
+    }
+
+    #[test]
+    fn writer() {
+        let mut writer = CountingWriter::new(io::Cursor::new(Vec::new()), System::Decimal);
+        let mut reader = &[7u8; 1_500_000][..];
+        assert_eq!(1_500_000, io::copy(&mut reader, &mut writer).unwrap());
+        writer.seek(SeekFrom::Start(0)).unwrap();
+        writer.write_all(&[1, 2, 3]).unwrap();
+        assert_eq!(1_500_003, writer.total());
+        assert!(writer.to_string().starts_with("1.50 MB at "));
+        assert_eq!(1_500_000, writer.into_inner().into_inner().len());
+    }
+}
@@ -1,10 +1,84 @@
+// Tests link `std` for the harness, so the `alloc`-only configuration can be tested as well.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 pub mod consts;
+pub mod convert;
+pub mod display;
+#[cfg(feature = "std")]
+pub mod io;
+pub mod locale;
+mod mantissa;
+pub mod meter;
+pub mod options;
+pub mod parse;
+pub mod parts;
+#[cfg(feature = "alloc")]
+pub mod progress;
+pub mod rate;
+pub mod size;
+
+pub use size::ByteSize;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::fmt;
 
 use consts::*;
+use display::SizeDisplay;
+use locale::Locale;
+use mantissa::{Fraction, Mantissa};
+use options::{FormatOptions, Precision, SignPolicy, UnitStyle, Units};
+use parts::SizeParts;
+
+/// Divisors of the magnitudes up to [`Magnitude::Exa`], which are the ones that fit into a `u64`.
+static U64_DIVISORS: [[u64; Magnitude::Exa as usize + 1]; 2] =
+    [u64_divisors(1000), u64_divisors(1024)];
+
+const fn u64_divisors(base: u64) -> [u64; Magnitude::Exa as usize + 1] {
+    let mut divisors = [1; Magnitude::Exa as usize + 1];
+    let mut i = 1;
+    while i < divisors.len() {
+        divisors[i] = divisors[i - 1] * base;
+        i += 1;
+    }
+    divisors
+}
+
+/// Collects the output of a `write_*` method into a new `String`.
+#[cfg(feature = "alloc")]
+fn collect(write: impl FnOnce(&mut String) -> fmt::Result) -> String {
+    let mut output = String::new();
+    write(&mut output).expect("writing to a String cannot fail");
+    output
+}
+
+/// The sign of a value being formatted. Unsigned values never get one.
+#[derive(Clone, Copy, PartialEq)]
+enum Sign {
+    Unsigned,
+    Positive,
+    Negative,
+}
+
+impl Sign {
+    fn of(negative: bool) -> Self {
+        if negative {
+            Sign::Negative
+        } else {
+            Sign::Positive
+        }
+    }
+}
 
 pub struct ByteSizeFormatter {
-    divisor: u64,
-    unit: String,
+    system: System,
+    magnitude: Magnitude,
+    divisor: u128,
+    unit: &'static str,
+    options: FormatOptions,
 }
 
 impl ByteSizeFormatter {
@@ -12,8 +86,8 @@ impl ByteSizeFormatter {
     ///
     /// # Arguments
     ///
-    /// * `system` - The numeral system (Binary or Decimal)
-    /// * `magnitude` - The magnitude (Kilo, Mega, Giga, etc.)
+    /// * `system` - The numeral system (Binary, Decimal or Jedec)
+    /// * `magnitude` - The magnitude (Byte, Kilo, Mega, Giga, etc.)
     ///
     /// # Example
     /// ```
@@ -27,33 +101,65 @@ impl ByteSizeFormatter {
     /// // Create a formatter for mebibytes (1024² bytes)
     /// let mib_formatter = ByteSizeFormatter::new(System::Binary, Magnitude::Mega);
     /// assert_eq!("1.00 MiB", mib_formatter.format_value(1024 * 1024));
+    ///
+    /// // JEDEC units are powers of 1024 without the infix
+    /// let jedec_formatter = ByteSizeFormatter::new(System::Jedec, Magnitude::Kilo);
+    /// assert_eq!("1.00 KB", jedec_formatter.format_value(1024));
+    ///
+    /// // Plain bytes are the same in both systems and never have decimals
+    /// let byte_formatter = ByteSizeFormatter::new(System::Binary, Magnitude::Byte);
+    /// assert_eq!("512 B", byte_formatter.format_value(512));
     /// ```
     ///
     /// # Returns
     ///
     /// A ByteSizeFormatter configured for the specified system and magnitude
-    pub fn new(system: System, magnitude: Magnitude) -> Self {
-        let infix = match system {
-            System::Binary => "i",
-            System::Decimal => "",
-        };
-        let magnitude = magnitude as usize;
+    pub const fn new(system: System, magnitude: Magnitude) -> Self {
         Self {
-            divisor: (system as u64).pow(magnitude as u32),
-            unit: format!("{}{infix}B", MAGNITUDE_PREFIXES[magnitude - 1]),
+            system,
+            magnitude,
+            divisor: Self::compute_divisor(system, magnitude),
+            unit: Locale::EN.symbol(system, magnitude, Units::Bytes),
+            options: FormatOptions::new(),
         }
     }
 
-    fn compute_divisor(system: System, magnitude: Magnitude) -> u64 {
-        (system as u64).pow(magnitude as u32)
+    /// Replaces the formatting options of this formatter.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::{Magnitude, System};
+    /// use bittenhumans::options::{FormatOptions, Separator};
+    ///
+    /// let formatter = ByteSizeFormatter::new(System::Binary, Magnitude::Giga).with_options(
+    ///     FormatOptions::new()
+    ///         .separator(Separator::NarrowNoBreakSpace)
+    ///         .trim_trailing_zeros(true),
+    /// );
+    /// assert_eq!("2\u{202F}GiB", formatter.format_value(2 * 1024 * 1024 * 1024));
+    /// ```
+    pub const fn with_options(mut self, options: FormatOptions) -> Self {
+        let locale = options.get_locale();
+        self.unit = match options.get_unit_style() {
+            UnitStyle::Symbol => locale.symbol(self.system, self.magnitude, options.get_units()),
+            UnitStyle::Name => locale.plural_name(self.system, self.magnitude, options.get_units()),
+        };
+        self.options = options;
+        self
+    }
+
+    const fn compute_divisor(system: System, magnitude: Magnitude) -> u128 {
+        (system.base() as u128).pow(magnitude as u32)
     }
 
     /// Creates a formatter for the largest magnitude that fits the given value under the specified numeral system.
+    /// Values below one kilo-unit get the unprefixed [`Magnitude::Byte`].
     ///
     /// # Arguments
     ///
     /// * `value` - The byte size value to fit
-    /// * `system` - The numeral system to use (Binary or Decimal)
+    /// * `system` - The numeral system to use (Binary, Decimal or Jedec)
     ///
     /// # Example
     /// ```
@@ -72,15 +178,155 @@ impl ByteSizeFormatter {
     ///
     /// A formatter configured with the appropriate magnitude for the value
     pub fn fit(value: u64, system: System) -> Self {
-        let mut last = Magnitude::Kilo;
-        for magnitude in enum_iterator::all::<Magnitude>() {
-            if (value as f64 / Self::compute_divisor(system, magnitude) as f64) < 1.0 {
+        Self::fit_with(value, system, FormatOptions::new())
+    }
+
+    /// Like [`fit`](Self::fit), but the returned formatter uses the given options.
+    pub fn fit_with(value: u64, system: System, options: FormatOptions) -> Self {
+        Self::fit_u128_with(value as u128, system, options)
+    }
+
+    /// Like [`fit`](Self::fit), but for values that may exceed a `u64`. These can select the
+    /// magnitudes beyond [`Magnitude::Exa`], whose divisors are only available through
+    /// [`checked_divisor`](Self::checked_divisor) and [`get_divisor_u128`](Self::get_divisor_u128).
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::System;
+    ///
+    /// let fleet_total = 3 * 10_u128.pow(24);
+    /// let formatter = ByteSizeFormatter::fit_u128(fleet_total, System::Decimal);
+    /// assert_eq!("3.00 YB", formatter.format_u128(fleet_total));
+    /// ```
+    pub fn fit_u128(value: u128, system: System) -> Self {
+        Self::fit_u128_with(value, system, FormatOptions::new())
+    }
+
+    /// Like [`fit_u128`](Self::fit_u128), but the returned formatter uses the given options.
+    pub fn fit_u128_with(value: u128, system: System, options: FormatOptions) -> Self {
+        Self::fit_abs(Fraction::integer(value), Sign::Unsigned, system, options)
+    }
+
+    /// Like [`fit`](Self::fit), but for a bit count rather than a byte count. Use it together
+    /// with [`Units::Bits`] options to show bit counts such as link speeds.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::System;
+    /// use bittenhumans::options::{BitSymbol, FormatOptions, Units};
+    ///
+    /// let options = FormatOptions::new().units(Units::Bits(BitSymbol::Long));
+    /// let formatter = ByteSizeFormatter::fit_bits_with(10_000_000_000, System::Decimal, options);
+    /// assert_eq!("10.00 Gbit", formatter.format_bits(10_000_000_000));
+    /// ```
+    pub fn fit_bits(bits: u64, system: System) -> Self {
+        Self::fit_bits_with(bits, system, FormatOptions::new())
+    }
+
+    /// Like [`fit_bits`](Self::fit_bits), but the returned formatter uses the given options.
+    pub fn fit_bits_with(bits: u64, system: System, options: FormatOptions) -> Self {
+        Self::fit_abs(
+            Fraction::bits(bits as u128),
+            Sign::Unsigned,
+            system,
+            options,
+        )
+    }
+
+    /// Like [`fit`](Self::fit), but for signed values. The magnitude is chosen based on the
+    /// absolute value.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::System;
+    ///
+    /// let formatter = ByteSizeFormatter::fit_i64(-1_500_000, System::Decimal);
+    /// assert_eq!("-1.50 MB", formatter.format_i64(-1_500_000));
+    /// assert_eq!("0.25 MB", formatter.format_i64(250_000));
+    /// ```
+    pub fn fit_i64(value: i64, system: System) -> Self {
+        Self::fit_i128_with(value as i128, system, FormatOptions::new())
+    }
+
+    /// Like [`fit_i64`](Self::fit_i64), but the returned formatter uses the given options.
+    pub fn fit_i64_with(value: i64, system: System, options: FormatOptions) -> Self {
+        Self::fit_i128_with(value as i128, system, options)
+    }
+
+    /// Like [`fit_i64`](Self::fit_i64), but for values that may exceed an `i64`.
+    pub fn fit_i128(value: i128, system: System) -> Self {
+        Self::fit_i128_with(value, system, FormatOptions::new())
+    }
+
+    /// Like [`fit_i128`](Self::fit_i128), but the returned formatter uses the given options.
+    pub fn fit_i128_with(value: i128, system: System, options: FormatOptions) -> Self {
+        let sign = Sign::of(value < 0);
+        Self::fit_abs(
+            Fraction::integer(value.unsigned_abs()),
+            sign,
+            system,
+            options,
+        )
+    }
+
+    /// Like [`fit`](Self::fit), but for fractional values such as averages and rates. Negative
+    /// values are fitted by their absolute value, NaN gets [`Magnitude::Byte`] and infinities the
+    /// largest magnitude.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::System;
+    ///
+    /// let formatter = ByteSizeFormatter::fit_f64(1536.5, System::Binary);
+    /// assert_eq!("1.50 KiB", formatter.format_f64(1536.5));
+    /// ```
+    pub fn fit_f64(value: f64, system: System) -> Self {
+        Self::fit_f64_with(value, system, FormatOptions::new())
+    }
+
+    /// Like [`fit_f64`](Self::fit_f64), but the returned formatter uses the given options.
+    pub fn fit_f64_with(value: f64, system: System, options: FormatOptions) -> Self {
+        if value.is_nan() {
+            return Self::new(system, Magnitude::Byte).with_options(options);
+        }
+        if value.is_infinite() {
+            let largest = enum_iterator::last::<Magnitude>().unwrap();
+            return Self::new(system, largest).with_options(options);
+        }
+        let sign = Sign::of(value.is_sign_negative());
+        Self::fit_abs(Fraction::from_f64(value), sign, system, options)
+    }
+
+    /// Fits the absolute value of a number with the given sign, which affects directed rounding.
+    /// The value is a byte count, even when `options` show bits.
+    fn fit_abs(value: Fraction, sign: Sign, system: System, options: FormatOptions) -> Self {
+        let mut last = Magnitude::Byte;
+        // Plain bytes or bits are always a fit, and every larger unit is a whole number of bytes.
+        for magnitude in enum_iterator::all::<Magnitude>().skip(1) {
+            let divisor = Self::compute_divisor(system, magnitude);
+            let unit_bytes = match options.get_units() {
+                Units::Bytes => divisor,
+                Units::Bits(_) => divisor / 8,
+            };
+            if value.floor() < unit_bytes {
                 break;
             }
             last = magnitude;
         }
 
-        Self::new(system, last)
+        // Rounding to the configured precision may carry the mantissa up to the system base
+        // ("1024.00 KiB"), in which case the next magnitude shows it as "1.00 MiB" instead.
+        let formatter = Self::new(system, last).with_options(options);
+        match enum_iterator::next(&last) {
+            Some(next) if formatter.mantissa(value, sign).integer >= system.base() as u128 => {
+                Self::new(system, next).with_options(options)
+            }
+            _ => formatter,
+        }
     }
 
     /// Formats a byte size value using the appropriate magnitude unit.
@@ -88,7 +334,7 @@ impl ByteSizeFormatter {
     /// # Arguments
     ///
     /// * `value` - The byte size value to format
-    /// * `system` - The numeral system to use (Binary or Decimal)
+    /// * `system` - The numeral system to use (Binary, Decimal or Jedec)
     ///
     /// # Example
     /// ```
@@ -102,35 +348,453 @@ impl ByteSizeFormatter {
     /// // Format a value using the binary system (powers of 1024)
     /// let formatted = ByteSizeFormatter::format_auto(1500000, System::Binary);
     /// assert_eq!("1.43 MiB", formatted);
+    ///
+    /// // Small values are shown as plain bytes
+    /// let formatted = ByteSizeFormatter::format_auto(512, System::Binary);
+    /// assert_eq!("512 B", formatted);
     /// ```
     ///
     /// # Returns
     ///
     /// A formatted string with the value and appropriate unit
+    #[cfg(feature = "alloc")]
     pub fn format_auto(value: u64, system: System) -> String {
-        Self::fit(value, system).format_value(value)
+        Self::format_auto_with(value, system, FormatOptions::new())
+    }
+
+    /// Like [`format_auto`](Self::format_auto), but formats with the given options.
+    #[cfg(feature = "alloc")]
+    pub fn format_auto_with(value: u64, system: System, options: FormatOptions) -> String {
+        Self::fit_with(value, system, options).format_value(value)
+    }
+
+    /// Like [`format_auto`](Self::format_auto), but for values that may exceed a `u64`.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::System;
+    ///
+    /// let formatted = ByteSizeFormatter::format_auto_u128(u128::MAX, System::Binary);
+    /// assert_eq!("268435456.00 QiB", formatted);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn format_auto_u128(value: u128, system: System) -> String {
+        Self::format_auto_u128_with(value, system, FormatOptions::new())
+    }
+
+    /// Like [`format_auto_u128`](Self::format_auto_u128), but formats with the given options.
+    #[cfg(feature = "alloc")]
+    pub fn format_auto_u128_with(value: u128, system: System, options: FormatOptions) -> String {
+        Self::fit_u128_with(value, system, options).format_u128(value)
+    }
+
+    /// Like [`format_auto`](Self::format_auto), but for signed values.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::System;
+    ///
+    /// assert_eq!("-512 B", ByteSizeFormatter::format_auto_i64(-512, System::Binary));
+    /// assert_eq!("-8.00 EiB", ByteSizeFormatter::format_auto_i64(i64::MIN, System::Binary));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn format_auto_i64(value: i64, system: System) -> String {
+        Self::format_auto_i128_with(value as i128, system, FormatOptions::new())
+    }
+
+    /// Like [`format_auto_i64`](Self::format_auto_i64), but formats with the given options.
+    #[cfg(feature = "alloc")]
+    pub fn format_auto_i64_with(value: i64, system: System, options: FormatOptions) -> String {
+        Self::format_auto_i128_with(value as i128, system, options)
+    }
+
+    /// Like [`format_auto_i64`](Self::format_auto_i64), but for values that may exceed an `i64`.
+    #[cfg(feature = "alloc")]
+    pub fn format_auto_i128(value: i128, system: System) -> String {
+        Self::format_auto_i128_with(value, system, FormatOptions::new())
+    }
+
+    /// Like [`format_auto_i128`](Self::format_auto_i128), but formats with the given options.
+    #[cfg(feature = "alloc")]
+    pub fn format_auto_i128_with(value: i128, system: System, options: FormatOptions) -> String {
+        Self::fit_i128_with(value, system, options).format_i128(value)
+    }
+
+    /// Like [`format_auto`](Self::format_auto), but for fractional values. See
+    /// [`format_f64`](Self::format_f64) for how special values are shown.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::System;
+    ///
+    /// let average = 3_000_000.0 / 7.0;
+    /// assert_eq!("428.57 KB", ByteSizeFormatter::format_auto_f64(average, System::Decimal));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn format_auto_f64(value: f64, system: System) -> String {
+        Self::format_auto_f64_with(value, system, FormatOptions::new())
     }
 
-    pub fn get_unit(&self) -> &str {
-        &self.unit
+    /// Like [`format_auto_f64`](Self::format_auto_f64), but formats with the given options.
+    #[cfg(feature = "alloc")]
+    pub fn format_auto_f64_with(value: f64, system: System, options: FormatOptions) -> String {
+        Self::fit_f64_with(value, system, options).format_f64(value)
     }
 
+    /// Like [`format_auto`](Self::format_auto), but for a bit count rather than a byte count.
+    #[cfg(feature = "alloc")]
+    pub fn format_auto_bits(bits: u64, system: System) -> String {
+        Self::format_auto_bits_with(bits, system, FormatOptions::new())
+    }
+
+    /// Like [`format_auto_bits`](Self::format_auto_bits), but formats with the given options.
+    #[cfg(feature = "alloc")]
+    pub fn format_auto_bits_with(bits: u64, system: System, options: FormatOptions) -> String {
+        Self::fit_bits_with(bits, system, options).format_bits(bits)
+    }
+
+    /// The unit label, in the last plural form of the locale (the English plural) when units are
+    /// spelled out.
+    pub const fn get_unit(&self) -> &'static str {
+        self.unit
+    }
+
+    pub const fn get_system(&self) -> System {
+        self.system
+    }
+
+    pub const fn get_magnitude(&self) -> Magnitude {
+        self.magnitude
+    }
+
+    /// Returns the divisor as a `u64`.
+    ///
+    /// # Panics
+    ///
+    /// If the magnitude is beyond [`Magnitude::Exa`], as its divisor does not fit into a `u64`.
+    /// Formatters from [`fit_u128`](Self::fit_u128) and the other wide variants may have such a
+    /// magnitude, so prefer [`checked_divisor`](Self::checked_divisor) or
+    /// [`get_divisor_u128`](Self::get_divisor_u128) for those.
     pub fn get_divisor(&self) -> &u64 {
-        &self.divisor
+        self.u64_divisor()
+            .expect("divisors beyond Exa do not fit into a u64")
+    }
+
+    /// Returns the divisor as a `u64`, or `None` if the magnitude is beyond [`Magnitude::Exa`].
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::{Magnitude, System};
+    ///
+    /// let formatter = ByteSizeFormatter::new(System::Binary, Magnitude::Mega);
+    /// assert_eq!(Some(1 << 20), formatter.checked_divisor());
+    ///
+    /// let formatter = ByteSizeFormatter::fit_u128(u128::MAX, System::Decimal);
+    /// assert_eq!(None, formatter.checked_divisor());
+    /// assert_eq!(10_u128.pow(30), formatter.get_divisor_u128());
+    /// ```
+    pub fn checked_divisor(&self) -> Option<u64> {
+        self.u64_divisor().copied()
+    }
+
+    fn u64_divisor(&self) -> Option<&u64> {
+        let divisors = &U64_DIVISORS[match self.system {
+            System::Decimal => 0,
+            System::Binary | System::Jedec => 1,
+        }];
+        divisors.get(self.magnitude as usize)
+    }
+
+    pub const fn get_divisor_u128(&self) -> u128 {
+        self.divisor
+    }
+
+    pub const fn get_options(&self) -> &FormatOptions {
+        &self.options
     }
 
+    #[cfg(feature = "alloc")]
     pub fn format_value(&self, value: u64) -> String {
-        format!("{:.2} {}", value as f64 / self.divisor as f64, self.unit)
+        collect(|output| self.write_to(output, value))
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn format_u128(&self, value: u128) -> String {
+        collect(|output| self.write_u128(output, value))
+    }
+
+    /// Formats a bit count rather than a byte count. With byte [`Units`] the value is divided
+    /// by eight.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::{Magnitude, System};
+    /// use bittenhumans::options::{BitSymbol, FormatOptions, Units};
+    ///
+    /// let formatter = ByteSizeFormatter::new(System::Decimal, Magnitude::Mega);
+    /// assert_eq!("12.50 MB", formatter.format_bits(100_000_000));
+    ///
+    /// let formatter = formatter.with_options(FormatOptions::new().units(Units::Bits(BitSymbol::Short)));
+    /// assert_eq!("100.00 Mb", formatter.format_bits(100_000_000));
+    /// assert_eq!("800.00 Mb", formatter.format_value(100_000_000));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn format_bits(&self, bits: u64) -> String {
+        collect(|output| self.write_bits(output, bits))
+    }
+
+    /// Formats a signed value, prefixing it with a sign according to the configured
+    /// [`SignPolicy`]. Values that display as zero are never signed.
+    #[cfg(feature = "alloc")]
+    pub fn format_i64(&self, value: i64) -> String {
+        collect(|output| self.write_i64(output, value))
+    }
+
+    #[cfg(feature = "alloc")]
+    pub fn format_i128(&self, value: i128) -> String {
+        collect(|output| self.write_i128(output, value))
+    }
+
+    /// Formats a fractional value, following the same precision and sign rules as the integer
+    /// methods. NaN is shown as `"NaN"` and infinities as `"inf"` in place of the number. Finite
+    /// values are converted exactly, except that magnitudes beyond `u128::MAX` saturate.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::{Magnitude, System};
+    ///
+    /// let formatter = ByteSizeFormatter::new(System::Binary, Magnitude::Mega);
+    /// assert_eq!("0.50 MiB", formatter.format_f64(524288.0));
+    /// assert_eq!("-0.25 MiB", formatter.format_f64(-262144.0));
+    /// assert_eq!("NaN MiB", formatter.format_f64(f64::NAN));
+    /// assert_eq!("-inf MiB", formatter.format_f64(f64::NEG_INFINITY));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn format_f64(&self, value: f64) -> String {
+        collect(|output| self.write_f64(output, value))
+    }
+
+    /// Writes a formatted value into `f` without allocating, e.g. into a reused buffer. Each
+    /// `format_*` method has a `write_*` counterpart, which is available without the `alloc`
+    /// feature.
+    ///
+    /// # Example
+    /// ```
+    /// use std::fmt::Write;
+    ///
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::{Magnitude, System};
+    ///
+    /// let formatter = ByteSizeFormatter::new(System::Binary, Magnitude::Mega);
+    /// let mut line = String::with_capacity(64);
+    /// write!(line, "rx ").unwrap();
+    /// formatter.write_to(&mut line, 1_500_000).unwrap();
+    /// assert_eq!("rx 1.43 MiB", line);
+    /// ```
+    pub fn write_to(&self, f: &mut impl fmt::Write, value: u64) -> fmt::Result {
+        self.write_u128(f, value as u128)
+    }
+
+    /// Like [`write_to`](Self::write_to), but for values that may exceed a `u64`.
+    pub fn write_u128(&self, f: &mut impl fmt::Write, value: u128) -> fmt::Result {
+        self.write_abs(f, Fraction::integer(value), Sign::Unsigned)
+    }
+
+    /// Like [`format_bits`](Self::format_bits), but writes into `f`.
+    pub fn write_bits(&self, f: &mut impl fmt::Write, bits: u64) -> fmt::Result {
+        self.write_abs(f, Fraction::bits(bits as u128), Sign::Unsigned)
+    }
+
+    /// Like [`write_to`](Self::write_to), but for a signed value.
+    pub fn write_i64(&self, f: &mut impl fmt::Write, value: i64) -> fmt::Result {
+        self.write_i128(f, value as i128)
+    }
+
+    /// Like [`write_i64`](Self::write_i64), but for values that may exceed an `i64`.
+    pub fn write_i128(&self, f: &mut impl fmt::Write, value: i128) -> fmt::Result {
+        let sign = Sign::of(value < 0);
+        self.write_abs(f, Fraction::integer(value.unsigned_abs()), sign)
+    }
+
+    /// Like [`write_to`](Self::write_to), but for a fractional value.
+    pub fn write_f64(&self, f: &mut impl fmt::Write, value: f64) -> fmt::Result {
+        let sign = Sign::of(value.is_sign_negative());
+        if value.is_finite() {
+            return self.write_abs(f, Fraction::from_f64(value), sign);
+        }
+
+        let number = match value.is_nan() {
+            true => "NaN",
+            false => {
+                f.write_str(self.sign_symbol(sign))?;
+                "inf"
+            }
+        };
+        f.write_str(number)?;
+        f.write_str(self.options.get_separator().as_str())?;
+        f.write_str(self.unit)
+    }
+
+    /// Splits a formatted value into its sign, digits and unit, which the `write_*` and
+    /// `format_*` methods assemble into a string.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::{Magnitude, System};
+    ///
+    /// let formatter = ByteSizeFormatter::new(System::Decimal, Magnitude::Giga);
+    /// let parts = formatter.parts(12_500_000_000);
+    /// assert_eq!((12, &[5, 0][..]), (parts.get_integer(), parts.get_fraction()));
+    /// assert_eq!((Magnitude::Giga, "GB"), (parts.get_magnitude(), parts.get_unit()));
+    /// assert!(parts.is_exact());
+    /// ```
+    pub fn parts(&self, value: u64) -> SizeParts {
+        self.parts_u128(value as u128)
+    }
+
+    /// Like [`parts`](Self::parts), but for values that may exceed a `u64`.
+    pub fn parts_u128(&self, value: u128) -> SizeParts {
+        self.parts_abs(Fraction::integer(value), Sign::Unsigned)
+    }
+
+    /// Like [`parts`](Self::parts), but for a bit count rather than a byte count.
+    pub fn parts_bits(&self, bits: u64) -> SizeParts {
+        self.parts_abs(Fraction::bits(bits as u128), Sign::Unsigned)
+    }
+
+    /// Like [`parts`](Self::parts), but for a signed value.
+    pub fn parts_i64(&self, value: i64) -> SizeParts {
+        self.parts_i128(value as i128)
+    }
+
+    /// Like [`parts_i64`](Self::parts_i64), but for values that may exceed an `i64`.
+    pub fn parts_i128(&self, value: i128) -> SizeParts {
+        let sign = Sign::of(value < 0);
+        self.parts_abs(Fraction::integer(value.unsigned_abs()), sign)
+    }
+
+    /// Like [`parts`](Self::parts), but for a fractional value. Returns `None` for NaN and
+    /// infinities, which have no digits.
+    pub fn parts_f64(&self, value: f64) -> Option<SizeParts> {
+        let sign = Sign::of(value.is_sign_negative());
+        value
+            .is_finite()
+            .then(|| self.parts_abs(Fraction::from_f64(value), sign))
+    }
+
+    /// Returns a [`Display`](fmt::Display) adapter for a value, which formats it without
+    /// allocating.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::{Magnitude, System};
+    ///
+    /// let formatter = ByteSizeFormatter::new(System::Decimal, Magnitude::Giga);
+    /// assert_eq!("free: 12.50 GB", format!("free: {}", formatter.display(12_500_000_000)));
+    /// ```
+    pub fn display(&self, value: u64) -> SizeDisplay<'_> {
+        SizeDisplay::new(self, value)
+    }
+
+    /// Writes the absolute value of a byte count.
+    fn write_abs(&self, f: &mut impl fmt::Write, value: Fraction, sign: Sign) -> fmt::Result {
+        write!(f, "{}", self.parts_abs(value, sign))
+    }
+
+    /// Splits the absolute value of a byte count.
+    fn parts_abs(&self, value: Fraction, sign: Sign) -> SizeParts {
+        let mantissa = self.mantissa(value, sign);
+        let trim_trailing_zeros = self.options.get_trim_trailing_zeros();
+        let sign = match mantissa.is_zero() {
+            true => "",
+            false => self.sign_symbol(sign),
+        };
+        let unit = match self.options.get_unit_style() {
+            UnitStyle::Symbol => self.unit,
+            UnitStyle::Name => self.options.get_locale().name(
+                self.system,
+                self.magnitude,
+                self.options.get_units(),
+                mantissa.integer,
+                mantissa.shown_fraction(trim_trailing_zeros),
+            ),
+        };
+        SizeParts::new(
+            sign,
+            mantissa,
+            self.system,
+            self.magnitude,
+            unit,
+            &self.options,
+        )
+    }
+
+    fn sign_symbol(&self, sign: Sign) -> &'static str {
+        match (sign, self.options.get_sign()) {
+            (Sign::Negative, _) => self.options.get_minus_sign().as_str(),
+            (Sign::Positive, SignPolicy::Always) => "+",
+            _ => "",
+        }
+    }
+
+    /// Computes the mantissa of the absolute byte count `value` in the configured units, rounded
+    /// to the configured precision.
+    fn mantissa(&self, value: Fraction, sign: Sign) -> Mantissa {
+        let mut saturated = false;
+        let (numerator, denominator) = match self.options.get_units() {
+            Units::Bytes => value.divided_by(self.divisor),
+            // Every prefixed divisor is a multiple of eight, so bits are counted by dividing by
+            // fewer bytes instead of multiplying the value, which could overflow.
+            Units::Bits(_) if self.divisor.is_multiple_of(8) => value.divided_by(self.divisor / 8),
+            // Plain bits beyond `u128::MAX` saturate, so the result is marked as inexact.
+            Units::Bits(_) => match value.checked_times(8) {
+                Some(bits) => bits.divided_by(self.divisor),
+                None => {
+                    saturated = true;
+                    Fraction::integer(u128::MAX).divided_by(self.divisor)
+                }
+            },
+        };
+        let rounding = match sign {
+            Sign::Negative => self.options.get_rounding().mirrored(),
+            _ => self.options.get_rounding(),
+        };
+        let mut mantissa = match self.options.get_precision() {
+            _ if self.divisor == 1 => Mantissa::new(numerator, denominator, 0, rounding),
+            Precision::Decimals(decimals) => {
+                Mantissa::new(numerator, denominator, decimals, rounding)
+            }
+            Precision::Significant(figures) => {
+                Mantissa::significant(numerator, denominator, figures, rounding)
+            }
+        };
+        mantissa.exact &= !saturated;
+        mantissa
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
 
     use super::*;
+    use options::{BitSymbol, MinusSign, RoundingMode, Separator};
 
     #[test]
     fn new() {
+        const GIBIBYTE: ByteSizeFormatter = ByteSizeFormatter::new(System::Binary, Magnitude::Giga)
+            .with_options(FormatOptions::new().precision(1));
+        assert_eq!("GiB", GIBIBYTE.get_unit());
+        assert_eq!(1 << 30, GIBIBYTE.get_divisor_u128());
+        assert_eq!("1.5 GiB", GIBIBYTE.format_value(3 << 29));
+
         let kibibyte = ByteSizeFormatter::new(System::Binary, Magnitude::Kilo);
         assert_eq!("KiB", kibibyte.get_unit());
         assert_eq!(1024_u64, *kibibyte.get_divisor());
@@ -142,7 +806,14 @@ mod tests {
 
     #[test]
     fn fit() {
-        let kibibyte = ByteSizeFormatter::fit(1, System::Binary);
+        let byte = ByteSizeFormatter::fit(1, System::Binary);
+        assert_eq!("B", byte.get_unit());
+        assert_eq!(1_u64, *byte.get_divisor());
+
+        let byte = ByteSizeFormatter::fit(1023, System::Binary);
+        assert_eq!("B", byte.get_unit());
+
+        let kibibyte = ByteSizeFormatter::fit(1024, System::Binary);
         assert_eq!("KiB", kibibyte.get_unit());
         assert_eq!(1024_u64, *kibibyte.get_divisor());
 
@@ -157,5 +828,564 @@ mod tests {
         assert_eq!("0.50 KiB".to_string(), kib.format_value(512));
         let gb = ByteSizeFormatter::new(System::Decimal, Magnitude::Giga);
         assert_eq!("1.00 GB".to_string(), gb.format_value(1_000_000_000));
+        let b = ByteSizeFormatter::new(System::Decimal, Magnitude::Byte);
+        assert_eq!("999 B".to_string(), b.format_value(999));
+        assert_eq!(
+            "0 B".to_string(),
+            ByteSizeFormatter::format_auto(0, System::Binary)
+        );
+    }
+
+    #[test]
+    fn jedec() {
+        let megabyte = ByteSizeFormatter::new(System::Jedec, Magnitude::Mega);
+        assert_eq!("MB", megabyte.get_unit());
+        assert_eq!(1 << 20, *megabyte.get_divisor());
+        assert_eq!(
+            "1.43 MB",
+            ByteSizeFormatter::format_auto(1_500_000, System::Jedec)
+        );
+        assert_eq!(
+            "1.00 MB",
+            ByteSizeFormatter::format_auto(1_048_575, System::Jedec)
+        );
+        assert_eq!(
+            "1000 B",
+            ByteSizeFormatter::format_auto(1000, System::Jedec)
+        );
+        assert_eq!(
+            "16.00 EB",
+            ByteSizeFormatter::format_auto(u64::MAX, System::Jedec)
+        );
+    }
+
+    #[test]
+    fn bits() {
+        let short = FormatOptions::new().units(Units::Bits(BitSymbol::Short));
+        let long = FormatOptions::new().units(Units::Bits(BitSymbol::Long));
+        for (system, unit) in [
+            (System::Decimal, "Kb"),
+            (System::Binary, "Kib"),
+            (System::Jedec, "Kb"),
+        ] {
+            let formatter = ByteSizeFormatter::new(system, Magnitude::Kilo).with_options(short);
+            assert_eq!(unit, formatter.get_unit());
+        }
+        let bit = ByteSizeFormatter::new(System::Binary, Magnitude::Byte).with_options(long);
+        assert_eq!("bit", bit.get_unit());
+        assert_eq!("8 bit", bit.format_value(1));
+        assert_eq!("1 bit", bit.format_bits(1));
+
+        assert_eq!(
+            "1.00 Kib",
+            ByteSizeFormatter::format_auto_with(128, System::Binary, short)
+        );
+        assert_eq!(
+            "1016 b",
+            ByteSizeFormatter::format_auto_with(127, System::Binary, short)
+        );
+        assert_eq!(
+            "98.40 Mbit",
+            ByteSizeFormatter::format_auto_bits_with(98_400_000, System::Decimal, long)
+        );
+        assert_eq!(
+            "12.30 MB",
+            ByteSizeFormatter::format_auto_bits(98_400_000, System::Decimal)
+        );
+        assert_eq!(
+            "2 B",
+            ByteSizeFormatter::format_auto_bits(12, System::Decimal)
+        );
+        assert_eq!(
+            "-1.00 Mib",
+            ByteSizeFormatter::format_auto_i64_with(-(1 << 17), System::Binary, short)
+        );
+
+        // Values beyond u128::MAX / 8 bytes still have an exact bit count in prefixed units.
+        assert_eq!(
+            "2722258935.37 Qb",
+            ByteSizeFormatter::format_auto_u128_with(u128::MAX, System::Decimal, short)
+        );
+        assert_eq!(
+            "340282366.92 Qb",
+            ByteSizeFormatter::format_auto_u128_with(u128::MAX / 8 + 1, System::Decimal, short)
+        );
+        assert_eq!(
+            "-1073741824.00 Qib",
+            ByteSizeFormatter::format_auto_i128_with(i128::MIN, System::Binary, short)
+        );
+        let qb = ByteSizeFormatter::new(System::Decimal, Magnitude::Quetta).with_options(short);
+        assert_eq!(2_722_258_935, qb.parts_u128(u128::MAX).get_integer());
+        assert!(qb
+            .parts_u128(1_000_000_000_000_000_000_000_000_000_000)
+            .is_exact());
+
+        // Plain bits saturate at u128::MAX and say so.
+        let plain = ByteSizeFormatter::new(System::Decimal, Magnitude::Byte).with_options(short);
+        let saturated = plain.parts_u128(u128::MAX / 4);
+        assert_eq!(u128::MAX, saturated.get_integer());
+        assert!(!saturated.is_exact());
+        assert!(plain.parts_u128(u128::MAX / 8).is_exact());
+        assert_eq!(
+            "4.00 Kbit",
+            ByteSizeFormatter::format_auto_f64_with(500.0, System::Decimal, long)
+        );
+
+        // u64::MAX bytes are still representable in bits.
+        assert_eq!(
+            "128.00 Eib",
+            ByteSizeFormatter::format_auto_with(u64::MAX, System::Binary, short)
+        );
+    }
+
+    #[test]
+    fn fit_rounding() {
+        assert_eq!(
+            "1.00 MiB",
+            ByteSizeFormatter::format_auto(1_048_575, System::Binary)
+        );
+        assert_eq!(
+            "1.00 MiB",
+            ByteSizeFormatter::format_auto(1_048_571, System::Binary)
+        );
+        assert_eq!(
+            "1023.99 KiB",
+            ByteSizeFormatter::format_auto(1_048_570, System::Binary)
+        );
+        assert_eq!(
+            "1.00 MB",
+            ByteSizeFormatter::format_auto(999_996, System::Decimal)
+        );
+        assert_eq!(
+            "999.99 KB",
+            ByteSizeFormatter::format_auto(999_994, System::Decimal)
+        );
+        assert_eq!(
+            "1023 B",
+            ByteSizeFormatter::format_auto(1023, System::Binary)
+        );
+
+        let whole = FormatOptions::new().precision(0);
+        assert_eq!(
+            "1 MB",
+            ByteSizeFormatter::format_auto_with(999_500, System::Decimal, whole)
+        );
+        assert_eq!(
+            "999 KB",
+            ByteSizeFormatter::format_auto_with(999_499, System::Decimal, whole)
+        );
+
+        let significant = FormatOptions::new().significant_figures(3);
+        assert_eq!(
+            "1.00 MiB",
+            ByteSizeFormatter::format_auto_with(1_048_064, System::Binary, significant)
+        );
+    }
+
+    #[test]
+    fn fit_boundaries() {
+        let precisions = [
+            FormatOptions::new(),
+            FormatOptions::new().precision(0),
+            FormatOptions::new().precision(1),
+            FormatOptions::new().precision(4),
+            FormatOptions::new().significant_figures(1),
+            FormatOptions::new().significant_figures(3),
+            FormatOptions::new().rounding(RoundingMode::Ceil),
+            FormatOptions::new().rounding(RoundingMode::Floor),
+            FormatOptions::new()
+                .significant_figures(2)
+                .rounding(RoundingMode::Ceil),
+        ];
+        for system in enum_iterator::all::<System>() {
+            let base = system.base() as u128;
+            for magnitude in enum_iterator::all::<Magnitude>().skip(1) {
+                let divisor = ByteSizeFormatter::compute_divisor(system, magnitude);
+                let window = (divisor / 50).max(1);
+                let step = (window / 500).max(1);
+                for options in precisions {
+                    let mut value = divisor - window;
+                    while value <= divisor.saturating_add(window) {
+                        let formatter = ByteSizeFormatter::fit_u128_with(value, system, options);
+                        let integer = formatter
+                            .mantissa(Fraction::integer(value), Sign::Unsigned)
+                            .integer;
+                        assert!(
+                            (1..base).contains(&integer),
+                            "{value} -> {}",
+                            formatter.format_u128(value)
+                        );
+                        value += step;
+                    }
+
+                    // The exact boundary and its neighbours are always checked.
+                    for value in [divisor - 1, divisor, divisor + 1] {
+                        let formatter = ByteSizeFormatter::fit_u128_with(value, system, options);
+                        assert!(
+                            formatter
+                                .mantissa(Fraction::integer(value), Sign::Unsigned)
+                                .integer
+                                < base
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn large_values() {
+        assert_eq!(
+            "16.00 EiB",
+            ByteSizeFormatter::format_auto(u64::MAX, System::Binary)
+        );
+        assert_eq!(
+            "18.45 EB",
+            ByteSizeFormatter::format_auto(u64::MAX, System::Decimal)
+        );
+
+        let precise =
+            |decimals, rounding| FormatOptions::new().precision(decimals).rounding(rounding);
+        let eib = ByteSizeFormatter::new(System::Binary, Magnitude::Exa);
+        let eb = ByteSizeFormatter::new(System::Decimal, Magnitude::Exa);
+        assert_eq!(
+            "18.44674407370955161500 EB",
+            eb.with_options(precise(20, RoundingMode::HalfEven))
+                .format_value(u64::MAX)
+        );
+
+        let eib = eib.with_options(precise(20, RoundingMode::HalfEven));
+        assert_eq!("15.99999999999999999913 EiB", eib.format_value(u64::MAX));
+        assert_eq!(
+            "15.99999999999999999827 EiB",
+            eib.format_value(u64::MAX - 1)
+        );
+        assert_eq!("3.00000000000000000087 EiB", eib.format_value(3 << 60 | 1));
+
+        let eib = eib.with_options(precise(18, RoundingMode::Ceil));
+        assert_eq!("3.000000000000000001 EiB", eib.format_value(3 << 60 | 1));
+        assert_eq!("16.000000000000000000 EiB", eib.format_value(u64::MAX));
+        let eib = eib.with_options(precise(18, RoundingMode::Floor));
+        assert_eq!("3.000000000000000000 EiB", eib.format_value(3 << 60 | 1));
+        assert_eq!("15.999999999999999999 EiB", eib.format_value(u64::MAX));
+
+        // Values just above 2^53 are no longer representable as f64.
+        let pb = ByteSizeFormatter::new(System::Decimal, Magnitude::Peta)
+            .with_options(precise(15, RoundingMode::HalfEven));
+        assert_eq!("9.007199254740993 PB", pb.format_value((1 << 53) + 1));
+
+        for system in enum_iterator::all::<System>() {
+            let exa = ByteSizeFormatter::compute_divisor(system, Magnitude::Exa) as u64;
+            for multiple in 1..=u64::MAX / exa {
+                let formatter = ByteSizeFormatter::fit(multiple * exa, system);
+                assert!(formatter.get_unit().starts_with('E'));
+                assert!(
+                    formatter
+                        .mantissa(Fraction::integer((multiple * exa) as u128), Sign::Unsigned)
+                        .exact
+                );
+                assert_eq!(
+                    format!("{multiple}.00"),
+                    formatter
+                        .format_value(multiple * exa)
+                        .split(' ')
+                        .next()
+                        .unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn wide_values() {
+        let quettabyte = ByteSizeFormatter::new(System::Decimal, Magnitude::Quetta);
+        assert_eq!("QB", quettabyte.get_unit());
+        assert_eq!(10_u128.pow(30), quettabyte.get_divisor_u128());
+        let zebibyte = ByteSizeFormatter::new(System::Binary, Magnitude::Zetta);
+        assert_eq!("ZiB", zebibyte.get_unit());
+        assert_eq!("0.02 ZiB", zebibyte.format_value(u64::MAX));
+
+        let format = ByteSizeFormatter::format_auto_u128;
+        assert_eq!("1.00 ZB", format(10_u128.pow(21), System::Decimal));
+        assert_eq!("1.00 ZiB", format(1 << 70, System::Binary));
+        assert_eq!("1.50 YiB", format(3 << 79, System::Binary));
+        assert_eq!("1.00 RB", format(10_u128.pow(27) - 1, System::Decimal));
+        assert_eq!("340282366.92 QB", format(u128::MAX, System::Decimal));
+
+        // The u64 API never picks a magnitude it cannot reach.
+        assert_eq!(
+            Magnitude::Exa,
+            ByteSizeFormatter::fit(u64::MAX, System::Decimal).get_magnitude()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn wide_divisor() {
+        ByteSizeFormatter::new(System::Binary, Magnitude::Zetta).get_divisor();
+    }
+
+    #[test]
+    fn checked_divisor() {
+        let exa = ByteSizeFormatter::new(System::Jedec, Magnitude::Exa);
+        assert_eq!(Some(1 << 60), exa.checked_divisor());
+        assert_eq!(
+            Some(1),
+            ByteSizeFormatter::fit(0, System::Decimal).checked_divisor()
+        );
+        let zetta = ByteSizeFormatter::new(System::Decimal, Magnitude::Zetta);
+        assert_eq!(None, zetta.checked_divisor());
+        assert_eq!(
+            None,
+            ByteSizeFormatter::fit_u128(u128::MAX, System::Binary).checked_divisor()
+        );
+    }
+
+    #[test]
+    fn signed() {
+        assert_eq!(
+            "-8.00 EiB",
+            ByteSizeFormatter::format_auto_i64(i64::MIN, System::Binary)
+        );
+        assert_eq!(
+            "-9.22 EB",
+            ByteSizeFormatter::format_auto_i64(i64::MIN, System::Decimal)
+        );
+        assert_eq!(
+            "8.00 EiB",
+            ByteSizeFormatter::format_auto_i64(i64::MAX, System::Binary)
+        );
+        assert_eq!(
+            "-134217728.00 QiB",
+            ByteSizeFormatter::format_auto_i128(i128::MIN, System::Binary)
+        );
+
+        let always = FormatOptions::new().sign(SignPolicy::Always);
+        let format = |value| ByteSizeFormatter::format_auto_i64_with(value, System::Binary, always);
+        assert_eq!("+1.20 GiB", format(1_288_490_189));
+        assert_eq!("-512 B", format(-512));
+        assert_eq!("+1 B", format(1));
+        assert_eq!("0 B", format(0));
+
+        let unicode = always.minus_sign(MinusSign::Unicode);
+        let kib = ByteSizeFormatter::new(System::Binary, Magnitude::Kilo).with_options(unicode);
+        assert_eq!("\u{2212}512.00 KiB", kib.format_i64(-512 * 1024));
+        // A delta too small to show is neither positive nor negative.
+        assert_eq!("0.00 KiB", kib.format_i64(-1));
+        assert_eq!("0.00 KiB", kib.format_i64(1));
+        // Unsigned values never get a sign.
+        assert_eq!("1.00 KiB", kib.format_value(1024));
+
+        // Directed rounding applies to the signed value.
+        let floor = FormatOptions::new()
+            .precision(1)
+            .rounding(RoundingMode::Floor);
+        let ceil = FormatOptions::new()
+            .precision(1)
+            .rounding(RoundingMode::Ceil);
+        let format = |value, options| {
+            ByteSizeFormatter::format_auto_i64_with(value, System::Decimal, options)
+        };
+        assert_eq!("-1.3 KB", format(-1250, floor));
+        assert_eq!("-1.2 KB", format(-1250, ceil));
+        assert_eq!("1.2 KB", format(1250, floor));
+        assert_eq!("-1.0 MB", format(-999_999, floor));
+        assert_eq!(
+            "-1000.0 KB",
+            ByteSizeFormatter::new(System::Decimal, Magnitude::Kilo)
+                .with_options(floor)
+                .format_i64(-999_999)
+        );
+        assert_eq!("-999.9 KB", format(-999_999, ceil));
+    }
+
+    #[test]
+    fn fractional() {
+        let format = |value| ByteSizeFormatter::format_auto_f64(value, System::Binary);
+        assert_eq!("1.43 MiB", format(1_500_000.0));
+        assert_eq!("1.00 MiB", format(1_048_575.9));
+        assert_eq!("1023 B", format(1023.4));
+        assert_eq!("1.00 KiB", format(1023.5));
+        assert_eq!("0 B", format(0.4));
+        assert_eq!("-2.50 GiB", format(-2.5 * (1 << 30) as f64));
+        assert_eq!("NaN B", format(f64::NAN));
+        assert_eq!("inf QiB", format(f64::INFINITY));
+        assert_eq!("-inf QiB", format(f64::NEG_INFINITY));
+        assert_eq!("0 B", format(-0.0));
+        assert_eq!("268435456.00 QiB", format(f64::MAX));
+
+        // Fractional inputs follow the integer API exactly where both can represent the value.
+        for value in [0_u64, 1, 999, 1024, 1_500_000, 1 << 40, (1 << 53) - 1] {
+            for system in enum_iterator::all::<System>() {
+                assert_eq!(
+                    ByteSizeFormatter::format_auto(value, system),
+                    ByteSizeFormatter::format_auto_f64(value as f64, system)
+                );
+            }
+        }
+
+        let mib = ByteSizeFormatter::new(System::Binary, Magnitude::Mega).with_options(
+            FormatOptions::new()
+                .precision(8)
+                .rounding(RoundingMode::Floor),
+        );
+        assert_eq!("0.00000009 MiB", mib.format_f64(0.1));
+        assert_eq!("-0.00000010 MiB", mib.format_f64(-0.1));
+
+        let always = FormatOptions::new().sign(SignPolicy::Always);
+        let formatter = ByteSizeFormatter::fit_f64_with(f64::INFINITY, System::Decimal, always);
+        assert_eq!("+inf QB", formatter.format_f64(f64::INFINITY));
+        assert_eq!("NaN QB", formatter.format_f64(f64::NAN));
+        assert_eq!("+0.01 QB", formatter.format_f64(1e28));
+        assert_eq!("0.00 QB", formatter.format_f64(1e27));
+    }
+
+    #[test]
+    fn rounding() {
+        let format = |value, system, rounding| {
+            let options = FormatOptions::new().rounding(rounding);
+            ByteSizeFormatter::format_auto_with(value, system, options)
+        };
+        assert_eq!(
+            "1.15 KB",
+            format(1150, System::Decimal, RoundingMode::Floor)
+        );
+        assert_eq!("1.15 KB", format(1150, System::Decimal, RoundingMode::Ceil));
+        assert_eq!(
+            "1.14 KB",
+            format(1144, System::Decimal, RoundingMode::HalfEven)
+        );
+        assert_eq!("1.15 KB", format(1149, System::Decimal, RoundingMode::Ceil));
+        assert_eq!(
+            "1.12 KB",
+            format(1125, System::Decimal, RoundingMode::HalfEven)
+        );
+        assert_eq!(
+            "1.13 KB",
+            format(1125, System::Decimal, RoundingMode::HalfUp)
+        );
+        assert_eq!(
+            "1.12 KB",
+            format(1129, System::Decimal, RoundingMode::Floor)
+        );
+
+        // Ceil reaches the next magnitude earlier, floor never does before the boundary.
+        assert_eq!(
+            "1.00 MiB",
+            format(1_048_566, System::Binary, RoundingMode::Ceil)
+        );
+        assert_eq!(
+            "1023.99 KiB",
+            format(1_048_575, System::Binary, RoundingMode::Floor)
+        );
+        assert_eq!(
+            "1.00 MiB",
+            format(1_048_576, System::Binary, RoundingMode::Floor)
+        );
+
+        let significant = FormatOptions::new()
+            .significant_figures(2)
+            .rounding(RoundingMode::Ceil);
+        assert_eq!(
+            "1.1 KB",
+            ByteSizeFormatter::format_auto_with(1001, System::Decimal, significant)
+        );
+    }
+
+    #[test]
+    fn options() {
+        let trimmed = FormatOptions::new().trim_trailing_zeros(true);
+        let format =
+            |value, system, options| ByteSizeFormatter::format_auto_with(value, system, options);
+        assert_eq!("1.5 MB", format(1_500_000, System::Decimal, trimmed));
+        assert_eq!("2 GiB", format(2 << 30, System::Binary, trimmed));
+        assert_eq!("1.43 MiB", format(1_500_000, System::Binary, trimmed));
+        assert_eq!("100 KB", format(100_000, System::Decimal, trimmed));
+
+        let precise = FormatOptions::new().precision(4).separator(Separator::None);
+        assert_eq!("1.4305MiB", format(1_500_000, System::Binary, precise));
+        assert_eq!("512B", format(512, System::Binary, precise));
+        assert_eq!("1.0000KiB", format(1024, System::Binary, precise));
+
+        let whole = FormatOptions::new().precision(0).trim_trailing_zeros(true);
+        assert_eq!("100 KB", format(100_000, System::Decimal, whole));
+
+        let significant = FormatOptions::new().significant_figures(3);
+        assert_eq!(
+            "954 MiB",
+            format(1_000_000_000, System::Binary, significant)
+        );
+        assert_eq!("10.0 KB", format(9_999, System::Decimal, significant));
+        assert_eq!("1.00 MB", format(1_000_000, System::Decimal, significant));
+        assert_eq!("999 B", format(999, System::Decimal, significant));
+        let mib = ByteSizeFormatter::new(System::Binary, Magnitude::Mega).with_options(significant);
+        assert_eq!("0.0977 MiB", mib.format_value(102_400));
+        assert_eq!("0.100 MiB", mib.format_value(104_857));
+        assert_eq!("0.00 MiB", mib.format_value(0));
+        assert_eq!("1024 MiB", mib.format_value(1 << 30));
+
+        let narrow = FormatOptions::new().separator(Separator::NarrowNoBreakSpace);
+        assert_eq!("1.50\u{202F}MB", format(1_500_000, System::Decimal, narrow));
+    }
+    #[test]
+    fn unit_names() {
+        let names = FormatOptions::new().unit_style(UnitStyle::Name);
+        for (system, expected) in [
+            (
+                System::Decimal,
+                ["byte", "kilobyte", "exabyte", "quettabyte"],
+            ),
+            (
+                System::Binary,
+                ["byte", "kibibyte", "exbibyte", "quebibyte"],
+            ),
+            (System::Jedec, ["byte", "kilobyte", "exabyte", "quettabyte"]),
+        ] {
+            for (magnitude, name) in [
+                Magnitude::Byte,
+                Magnitude::Kilo,
+                Magnitude::Exa,
+                Magnitude::Quetta,
+            ]
+            .into_iter()
+            .zip(expected)
+            {
+                let formatter =
+                    ByteSizeFormatter::new(system, magnitude).with_options(names.precision(0));
+                assert_eq!(format!("{name}s"), formatter.get_unit());
+                let one = formatter.get_divisor_u128();
+                assert_eq!(name, formatter.parts_u128(one).get_unit());
+            }
+        }
+
+        let format =
+            |value, options| ByteSizeFormatter::format_auto_with(value, System::Binary, options);
+        assert_eq!("1 byte", format(1, names));
+        assert_eq!("0 bytes", format(0, names));
+        assert_eq!("2 bytes", format(2, names));
+        assert_eq!("1.00 kibibytes", format(1024, names));
+        assert_eq!("1.43 mebibytes", format(1_500_000, names));
+        let trimmed = names.trim_trailing_zeros(true);
+        assert_eq!("1 kibibyte", format(1024, trimmed));
+        assert_eq!("1 kibibyte", format(1025, trimmed));
+        assert_eq!("1.5 kibibytes", format(1536, trimmed));
+        assert_eq!("2 kibibytes", format(2048, trimmed));
+        let whole = names.precision(0);
+        assert_eq!("1 mebibyte", format(1_500_000, whole));
+
+        let formatter =
+            ByteSizeFormatter::new(System::Decimal, Magnitude::Byte).with_options(names);
+        assert_eq!("-1 byte", formatter.format_i64(-1));
+        assert_eq!("NaN bytes", formatter.format_f64(f64::NAN));
+
+        let bits = names.units(Units::Bits(BitSymbol::Short)).precision(1);
+        assert_eq!(
+            "1.5 megabits",
+            ByteSizeFormatter::format_auto_with(187_500, System::Decimal, bits)
+        );
+        assert_eq!(
+            "1 gibibit",
+            ByteSizeFormatter::format_auto_bits_with(1 << 30, System::Binary, bits.precision(0))
+        );
     }
 }
@@ -0,0 +1,870 @@
+use core::fmt;
+
+use crate::consts::{Magnitude, System};
+use crate::options::{BitSymbol, Units};
+
+/// The number of magnitudes, from [`Magnitude::Byte`] to [`Magnitude::Quetta`].
+pub const MAGNITUDES: usize = Magnitude::Quetta as usize + 1;
+
+/// Unit symbols indexed by infix (decimal or binary), unit symbol (bytes, short bits, long bits)
+/// and magnitude, e.g. `symbols[1][0][2]` is `"MiB"` in English. JEDEC units use the decimal
+/// symbols.
+pub type Symbols = [[[&'static str; MAGNITUDES]; 3]; 2];
+
+/// Spelled-out unit names in one plural form, indexed by infix (decimal or binary), unit (bytes
+/// or bits) and magnitude, e.g. `names[1][0][2]` is `"mebibytes"` in the English plural.
+pub type Names = [[[&'static str; MAGNITUDES]; 2]; 2];
+
+static EN_SYMBOLS: Symbols = [
+    [
+        [
+            "B", "KB", "MB", "GB", "TB", "PB", "EB", "ZB", "YB", "RB", "QB",
+        ],
+        [
+            "b", "Kb", "Mb", "Gb", "Tb", "Pb", "Eb", "Zb", "Yb", "Rb", "Qb",
+        ],
+        [
+            "bit", "Kbit", "Mbit", "Gbit", "Tbit", "Pbit", "Ebit", "Zbit", "Ybit", "Rbit", "Qbit",
+        ],
+    ],
+    [
+        [
+            "B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB", "ZiB", "YiB", "RiB", "QiB",
+        ],
+        [
+            "b", "Kib", "Mib", "Gib", "Tib", "Pib", "Eib", "Zib", "Yib", "Rib", "Qib",
+        ],
+        [
+            "bit", "Kibit", "Mibit", "Gibit", "Tibit", "Pibit", "Eibit", "Zibit", "Yibit", "Ribit",
+            "Qibit",
+        ],
+    ],
+];
+
+static EN_NAMES: [Names; 2] = [
+    [
+        [
+            [
+                "byte",
+                "kilobyte",
+                "megabyte",
+                "gigabyte",
+                "terabyte",
+                "petabyte",
+                "exabyte",
+                "zettabyte",
+                "yottabyte",
+                "ronnabyte",
+                "quettabyte",
+            ],
+            [
+                "bit",
+                "kilobit",
+                "megabit",
+                "gigabit",
+                "terabit",
+                "petabit",
+                "exabit",
+                "zettabit",
+                "yottabit",
+                "ronnabit",
+                "quettabit",
+            ],
+        ],
+        [
+            [
+                "byte",
+                "kibibyte",
+                "mebibyte",
+                "gibibyte",
+                "tebibyte",
+                "pebibyte",
+                "exbibyte",
+                "zebibyte",
+                "yobibyte",
+                "robibyte",
+                "quebibyte",
+            ],
+            [
+                "bit", "kibibit", "mebibit", "gibibit", "tebibit", "pebibit", "exbibit", "zebibit",
+                "yobibit", "robibit", "quebibit",
+            ],
+        ],
+    ],
+    [
+        [
+            [
+                "bytes",
+                "kilobytes",
+                "megabytes",
+                "gigabytes",
+                "terabytes",
+                "petabytes",
+                "exabytes",
+                "zettabytes",
+                "yottabytes",
+                "ronnabytes",
+                "quettabytes",
+            ],
+            [
+                "bits",
+                "kilobits",
+                "megabits",
+                "gigabits",
+                "terabits",
+                "petabits",
+                "exabits",
+                "zettabits",
+                "yottabits",
+                "ronnabits",
+                "quettabits",
+            ],
+        ],
+        [
+            [
+                "bytes",
+                "kibibytes",
+                "mebibytes",
+                "gibibytes",
+                "tebibytes",
+                "pebibytes",
+                "exbibytes",
+                "zebibytes",
+                "yobibytes",
+                "robibytes",
+                "quebibytes",
+            ],
+            [
+                "bits",
+                "kibibits",
+                "mebibits",
+                "gibibits",
+                "tebibits",
+                "pebibits",
+                "exbibits",
+                "zebibits",
+                "yobibits",
+                "robibits",
+                "quebibits",
+            ],
+        ],
+    ],
+];
+
+static FR_SYMBOLS: Symbols = [
+    [
+        [
+            "o", "Ko", "Mo", "Go", "To", "Po", "Eo", "Zo", "Yo", "Ro", "Qo",
+        ],
+        [
+            "b", "Kb", "Mb", "Gb", "Tb", "Pb", "Eb", "Zb", "Yb", "Rb", "Qb",
+        ],
+        [
+            "bit", "Kbit", "Mbit", "Gbit", "Tbit", "Pbit", "Ebit", "Zbit", "Ybit", "Rbit", "Qbit",
+        ],
+    ],
+    [
+        [
+            "o", "Kio", "Mio", "Gio", "Tio", "Pio", "Eio", "Zio", "Yio", "Rio", "Qio",
+        ],
+        [
+            "b", "Kib", "Mib", "Gib", "Tib", "Pib", "Eib", "Zib", "Yib", "Rib", "Qib",
+        ],
+        [
+            "bit", "Kibit", "Mibit", "Gibit", "Tibit", "Pibit", "Eibit", "Zibit", "Yibit", "Ribit",
+            "Qibit",
+        ],
+    ],
+];
+
+static FR_NAMES: [Names; 2] = [
+    [
+        [
+            [
+                "octet",
+                "kilooctet",
+                "mégaoctet",
+                "gigaoctet",
+                "téraoctet",
+                "pétaoctet",
+                "exaoctet",
+                "zettaoctet",
+                "yottaoctet",
+                "ronnaoctet",
+                "quettaoctet",
+            ],
+            [
+                "bit",
+                "kilobit",
+                "mégabit",
+                "gigabit",
+                "térabit",
+                "pétabit",
+                "exabit",
+                "zettabit",
+                "yottabit",
+                "ronnabit",
+                "quettabit",
+            ],
+        ],
+        [
+            [
+                "octet",
+                "kibioctet",
+                "mébioctet",
+                "gibioctet",
+                "tébioctet",
+                "pébioctet",
+                "exbioctet",
+                "zébioctet",
+                "yobioctet",
+                "robioctet",
+                "québioctet",
+            ],
+            [
+                "bit",
+                "kibibit",
+                "mébibit",
+                "gibibit",
+                "tébibit",
+                "pébibit",
+                "exbibit",
+                "zébibit",
+                "yobibit",
+                "robibit",
+                "québibit",
+            ],
+        ],
+    ],
+    [
+        [
+            [
+                "octets",
+                "kilooctets",
+                "mégaoctets",
+                "gigaoctets",
+                "téraoctets",
+                "pétaoctets",
+                "exaoctets",
+                "zettaoctets",
+                "yottaoctets",
+                "ronnaoctets",
+                "quettaoctets",
+            ],
+            [
+                "bits",
+                "kilobits",
+                "mégabits",
+                "gigabits",
+                "térabits",
+                "pétabits",
+                "exabits",
+                "zettabits",
+                "yottabits",
+                "ronnabits",
+                "quettabits",
+            ],
+        ],
+        [
+            [
+                "octets",
+                "kibioctets",
+                "mébioctets",
+                "gibioctets",
+                "tébioctets",
+                "pébioctets",
+                "exbioctets",
+                "zébioctets",
+                "yobioctets",
+                "robioctets",
+                "québioctets",
+            ],
+            [
+                "bits",
+                "kibibits",
+                "mébibits",
+                "gibibits",
+                "tébibits",
+                "pébibits",
+                "exbibits",
+                "zébibits",
+                "yobibits",
+                "robibits",
+                "québibits",
+            ],
+        ],
+    ],
+];
+
+static RU_SYMBOLS: Symbols = [
+    [
+        [
+            "Б", "КБ", "МБ", "ГБ", "ТБ", "ПБ", "ЭБ", "ЗБ", "ИБ", "РБ", "КвБ",
+        ],
+        [
+            "бит",
+            "Кбит",
+            "Мбит",
+            "Гбит",
+            "Тбит",
+            "Пбит",
+            "Эбит",
+            "Збит",
+            "Ибит",
+            "Рбит",
+            "Квбит",
+        ],
+        [
+            "бит",
+            "Кбит",
+            "Мбит",
+            "Гбит",
+            "Тбит",
+            "Пбит",
+            "Эбит",
+            "Збит",
+            "Ибит",
+            "Рбит",
+            "Квбит",
+        ],
+    ],
+    [
+        [
+            "Б", "КиБ", "МиБ", "ГиБ", "ТиБ", "ПиБ", "ЭиБ", "ЗиБ", "ИиБ", "РиБ", "КвиБ",
+        ],
+        [
+            "бит",
+            "Кибит",
+            "Мибит",
+            "Гибит",
+            "Тибит",
+            "Пибит",
+            "Эибит",
+            "Зибит",
+            "Иибит",
+            "Рибит",
+            "Квибит",
+        ],
+        [
+            "бит",
+            "Кибит",
+            "Мибит",
+            "Гибит",
+            "Тибит",
+            "Пибит",
+            "Эибит",
+            "Зибит",
+            "Иибит",
+            "Рибит",
+            "Квибит",
+        ],
+    ],
+];
+
+static RU_NAMES: [Names; 3] = [
+    [
+        [
+            [
+                "байт",
+                "килобайт",
+                "мегабайт",
+                "гигабайт",
+                "терабайт",
+                "петабайт",
+                "эксабайт",
+                "зеттабайт",
+                "йоттабайт",
+                "роннабайт",
+                "кветтабайт",
+            ],
+            [
+                "бит",
+                "килобит",
+                "мегабит",
+                "гигабит",
+                "терабит",
+                "петабит",
+                "эксабит",
+                "зеттабит",
+                "йоттабит",
+                "роннабит",
+                "кветтабит",
+            ],
+        ],
+        [
+            [
+                "байт",
+                "кибибайт",
+                "мебибайт",
+                "гибибайт",
+                "тебибайт",
+                "пебибайт",
+                "эксбибайт",
+                "зебибайт",
+                "йобибайт",
+                "робибайт",
+                "квебибайт",
+            ],
+            [
+                "бит",
+                "кибибит",
+                "мебибит",
+                "гибибит",
+                "тебибит",
+                "пебибит",
+                "эксбибит",
+                "зебибит",
+                "йобибит",
+                "робибит",
+                "квебибит",
+            ],
+        ],
+    ],
+    [
+        [
+            [
+                "байта",
+                "килобайта",
+                "мегабайта",
+                "гигабайта",
+                "терабайта",
+                "петабайта",
+                "эксабайта",
+                "зеттабайта",
+                "йоттабайта",
+                "роннабайта",
+                "кветтабайта",
+            ],
+            [
+                "бита",
+                "килобита",
+                "мегабита",
+                "гигабита",
+                "терабита",
+                "петабита",
+                "эксабита",
+                "зеттабита",
+                "йоттабита",
+                "роннабита",
+                "кветтабита",
+            ],
+        ],
+        [
+            [
+                "байта",
+                "кибибайта",
+                "мебибайта",
+                "гибибайта",
+                "тебибайта",
+                "пебибайта",
+                "эксбибайта",
+                "зебибайта",
+                "йобибайта",
+                "робибайта",
+                "квебибайта",
+            ],
+            [
+                "бита",
+                "кибибита",
+                "мебибита",
+                "гибибита",
+                "тебибита",
+                "пебибита",
+                "эксбибита",
+                "зебибита",
+                "йобибита",
+                "робибита",
+                "квебибита",
+            ],
+        ],
+    ],
+    [
+        [
+            [
+                "байт",
+                "килобайт",
+                "мегабайт",
+                "гигабайт",
+                "терабайт",
+                "петабайт",
+                "эксабайт",
+                "зеттабайт",
+                "йоттабайт",
+                "роннабайт",
+                "кветтабайт",
+            ],
+            [
+                "бит",
+                "килобит",
+                "мегабит",
+                "гигабит",
+                "терабит",
+                "петабит",
+                "эксабит",
+                "зеттабит",
+                "йоттабит",
+                "роннабит",
+                "кветтабит",
+            ],
+        ],
+        [
+            [
+                "байт",
+                "кибибайт",
+                "мебибайт",
+                "гибибайт",
+                "тебибайт",
+                "пебибайт",
+                "эксбибайт",
+                "зебибайт",
+                "йобибайт",
+                "робибайт",
+                "квебибайт",
+            ],
+            [
+                "бит",
+                "кибибит",
+                "мебибит",
+                "гибибит",
+                "тебибит",
+                "пебибит",
+                "эксбибит",
+                "зебибит",
+                "йобибит",
+                "робибит",
+                "квебибит",
+            ],
+        ],
+    ],
+];
+
+/// Selects the plural form of `integer` followed by the shown fractional digits `fraction`.
+fn english_plural(integer: u128, fraction: &[u8]) -> usize {
+    match (integer, fraction.is_empty()) {
+        (1, true) => 0,
+        _ => 1,
+    }
+}
+
+/// Numbers below two are singular in French, even with decimals: "1,5 kilooctet".
+fn french_plural(integer: u128, _fraction: &[u8]) -> usize {
+    match integer {
+        0 | 1 => 0,
+        _ => 1,
+    }
+}
+
+/// Russian has forms for numbers ending in one ("1 байт"), in two to four ("2 байта") and the rest
+/// ("5 байт"). Fractions take the second form: "1,5 байта".
+fn russian_plural(integer: u128, fraction: &[u8]) -> usize {
+    if !fraction.is_empty() {
+        return 1;
+    }
+    match (integer % 10, integer % 100) {
+        (1, tens) if tens != 11 => 0,
+        (2..=4, tens) if !(12..=14).contains(&tens) => 1,
+        _ => 2,
+    }
+}
+
+/// The unit symbols, unit names and number conventions of a language.
+///
+/// Built-in locales are available as associated constants and through
+/// [`from_tag`](Self::from_tag). Other languages are added by creating a `Locale` from static
+/// tables with [`new`](Self::new) and passing it to
+/// [`FormatOptions::locale`](crate::options::FormatOptions::locale).
+///
+/// # Example
+/// ```
+/// use bittenhumans::ByteSizeFormatter;
+/// use bittenhumans::consts::System;
+/// use bittenhumans::locale::Locale;
+/// use bittenhumans::options::{FormatOptions, UnitStyle};
+///
+/// let french = FormatOptions::new().locale(&Locale::FR);
+/// assert_eq!("1,50 Mo", ByteSizeFormatter::format_auto_with(1_500_000, System::Decimal, french));
+/// assert_eq!("1,43 Mio", ByteSizeFormatter::format_auto_with(1_500_000, System::Binary, french));
+///
+/// let russian = FormatOptions::new().locale(&Locale::RU).precision(0);
+/// let format = |value| ByteSizeFormatter::format_auto_with(value, System::Decimal, russian);
+/// assert_eq!("2 ГБ", format(2_000_000_000));
+///
+/// let russian = russian.unit_style(UnitStyle::Name);
+/// let format = |value| ByteSizeFormatter::format_auto_with(value, System::Decimal, russian);
+/// assert_eq!("1 гигабайт", format(1_000_000_000));
+/// assert_eq!("3 гигабайта", format(3_000_000_000));
+/// assert_eq!("5 гигабайт", format(5_000_000_000));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Locale {
+    symbols: &'static Symbols,
+    names: &'static [Names],
+    plural: fn(u128, &[u8]) -> usize,
+    decimal_point: char,
+}
+
+impl Locale {
+    /// English: `"1.50 MB"`, `"1 kibibyte"`, `"1.50 megabytes"`.
+    pub const EN: Locale = Locale::new(&EN_SYMBOLS, &EN_NAMES, english_plural, '.');
+
+    /// French, with octets: `"1,50 Mo"`, `"1 kibioctet"`, `"2 mégaoctets"`.
+    pub const FR: Locale = Locale::new(&FR_SYMBOLS, &FR_NAMES, french_plural, ',');
+
+    /// Russian: `"1,50 МБ"`, `"1 кибибайт"`, `"2 мегабайта"`, `"5 мегабайт"`. Bits are always
+    /// written as `"Мбит"`, since the short symbol would be ambiguous.
+    pub const RU: Locale = Locale::new(&RU_SYMBOLS, &RU_NAMES, russian_plural, ',');
+
+    /// Creates a locale from its tables.
+    ///
+    /// # Arguments
+    ///
+    /// * `symbols` - The unit symbols
+    /// * `names` - The spelled-out unit names, one table per plural form
+    /// * `plural` - Selects the index of the plural form in `names` for a number, given its
+    ///   integer part and its shown fractional digits (from 0 to 9). Indices beyond the last form
+    ///   select the last form
+    /// * `decimal_point` - The character between the integer and fractional digits
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::System;
+    /// use bittenhumans::locale::{Locale, Names, Symbols};
+    /// use bittenhumans::options::FormatOptions;
+    ///
+    /// // German uses the English symbols with a decimal comma.
+    /// static SYMBOLS: Symbols = *Locale::EN.get_symbols();
+    /// static NAMES: [Names; 1] = [*Locale::EN.get_names().last().unwrap()];
+    /// static GERMAN: Locale = Locale::new(&SYMBOLS, &NAMES, |_, _| 0, ',');
+    ///
+    /// let options = FormatOptions::new().locale(&GERMAN);
+    /// assert_eq!("1,43 MiB", ByteSizeFormatter::format_auto_with(1_500_000, System::Binary, options));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `names` is empty.
+    pub const fn new(
+        symbols: &'static Symbols,
+        names: &'static [Names],
+        plural: fn(u128, &[u8]) -> usize,
+        decimal_point: char,
+    ) -> Self {
+        assert!(!names.is_empty(), "locale without unit names");
+        Self {
+            symbols,
+            names,
+            plural,
+            decimal_point,
+        }
+    }
+
+    /// Looks up a built-in locale by its language tag, e.g. `"fr"`, `"fr-CA"` or `"ru_RU"`.
+    /// Only the language is considered, ignoring case.
+    pub fn from_tag(tag: &str) -> Option<&'static Locale> {
+        let language = tag.split(['-', '_']).next().unwrap_or_default();
+        [
+            ("en", &Locale::EN),
+            ("fr", &Locale::FR),
+            ("ru", &Locale::RU),
+        ]
+        .into_iter()
+        .find(|(code, _)| code.eq_ignore_ascii_case(language))
+        .map(|(_, locale)| locale)
+    }
+
+    pub const fn get_symbols(&self) -> &'static Symbols {
+        self.symbols
+    }
+
+    pub const fn get_names(&self) -> &'static [Names] {
+        self.names
+    }
+
+    pub const fn get_decimal_point(&self) -> char {
+        self.decimal_point
+    }
+
+    /// The symbol of a unit, e.g. `"MiB"`.
+    pub const fn symbol(&self, system: System, magnitude: Magnitude, units: Units) -> &'static str {
+        let symbol = match units {
+            Units::Bytes => 0,
+            Units::Bits(BitSymbol::Short) => 1,
+            Units::Bits(BitSymbol::Long) => 2,
+        };
+        self.symbols[infix(system)][symbol][magnitude as usize]
+    }
+
+    /// The name of a unit in the plural form for a number, given its integer part and its shown
+    /// fractional digits.
+    pub fn name(
+        &self,
+        system: System,
+        magnitude: Magnitude,
+        units: Units,
+        integer: u128,
+        fraction: &[u8],
+    ) -> &'static str {
+        let form = (self.plural)(integer, fraction).min(self.names.len() - 1);
+        self.name_in_form(system, magnitude, units, form)
+    }
+
+    /// The name of a unit in the last plural form, which is the English plural.
+    pub(crate) const fn plural_name(
+        &self,
+        system: System,
+        magnitude: Magnitude,
+        units: Units,
+    ) -> &'static str {
+        self.name_in_form(system, magnitude, units, self.names.len() - 1)
+    }
+
+    const fn name_in_form(
+        &self,
+        system: System,
+        magnitude: Magnitude,
+        units: Units,
+        form: usize,
+    ) -> &'static str {
+        let unit = match units {
+            Units::Bytes => 0,
+            Units::Bits(_) => 1,
+        };
+        self.names[form][infix(system)][unit][magnitude as usize]
+    }
+}
+
+impl PartialEq for Locale {
+    fn eq(&self, other: &Self) -> bool {
+        self.symbols == other.symbols
+            && self.names == other.names
+            && core::ptr::fn_addr_eq(self.plural, other.plural)
+            && self.decimal_point == other.decimal_point
+    }
+}
+
+impl Eq for Locale {}
+
+/// Shows the decimal byte symbols rather than all the tables.
+impl fmt::Debug for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Locale")
+            .field("symbols", &self.symbols[0][0])
+            .field("decimal_point", &self.decimal_point)
+            .finish_non_exhaustive()
+    }
+}
+
+const fn infix(system: System) -> usize {
+    match system {
+        System::Decimal | System::Jedec => 0,
+        System::Binary => 1,
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plurals() {
+        let forms = |plural: fn(u128, &[u8]) -> usize| {
+            [
+                (0, &[][..]),
+                (1, &[]),
+                (1, &[0]),
+                (2, &[]),
+                (5, &[]),
+                (11, &[]),
+                (21, &[]),
+                (22, &[]),
+            ]
+            .map(|(integer, fraction)| plural(integer, fraction))
+        };
+        assert_eq!([1, 0, 1, 1, 1, 1, 1, 1], forms(english_plural));
+        assert_eq!([0, 0, 0, 1, 1, 1, 1, 1], forms(french_plural));
+        assert_eq!([2, 0, 1, 1, 2, 2, 0, 1], forms(russian_plural));
+        assert_eq!(2, russian_plural(112, &[]));
+        assert_eq!(2, russian_plural(114, &[]));
+    }
+
+    #[test]
+    fn tables() {
+        let mib = (System::Binary, Magnitude::Mega);
+        let long = Units::Bits(BitSymbol::Long);
+        assert_eq!("MiB", Locale::EN.symbol(mib.0, mib.1, Units::Bytes));
+        assert_eq!("Mio", Locale::FR.symbol(mib.0, mib.1, Units::Bytes));
+        assert_eq!("МиБ", Locale::RU.symbol(mib.0, mib.1, Units::Bytes));
+        assert_eq!("Mibit", Locale::FR.symbol(mib.0, mib.1, long));
+        assert_eq!(
+            "КвБ",
+            Locale::RU.symbol(System::Jedec, Magnitude::Quetta, Units::Bytes)
+        );
+        assert_eq!(
+            "mébioctets",
+            Locale::FR.name(mib.0, mib.1, Units::Bytes, 2, &[])
+        );
+        assert_eq!("мебибита", Locale::RU.name(mib.0, mib.1, long, 1, &[5]));
+        assert_eq!(
+            "bytes",
+            Locale::EN.plural_name(System::Decimal, Magnitude::Byte, Units::Bytes)
+        );
+        assert_eq!(
+            "килобайт",
+            Locale::RU.plural_name(System::Decimal, Magnitude::Kilo, Units::Bytes)
+        );
+
+        assert_eq!(Some(&Locale::FR), Locale::from_tag("fr-CA"));
+        assert_eq!(Some(&Locale::RU), Locale::from_tag("RU_ru"));
+        assert_eq!(Some(&Locale::EN), Locale::from_tag("en"));
+        assert_eq!(None, Locale::from_tag("de"));
+        assert_eq!(None, Locale::from_tag(""));
+    }
+
+    #[test]
+    fn custom() {
+        static NAMES: [Names; 1] = [EN_NAMES[1]];
+        static SINGLE: Locale =
+            Locale::new(&EN_SYMBOLS, &NAMES, |integer, _| integer as usize, '.');
+
+        let kb = (System::Decimal, Magnitude::Kilo, Units::Bytes);
+        assert_eq!("kilobytes", SINGLE.name(kb.0, kb.1, kb.2, 0, &[]));
+        assert_eq!("kilobytes", SINGLE.name(kb.0, kb.1, kb.2, 7, &[]));
+        assert_eq!("kilobytes", SINGLE.plural_name(kb.0, kb.1, kb.2));
+
+        let options = crate::options::FormatOptions::new()
+            .locale(&SINGLE)
+            .unit_style(crate::options::UnitStyle::Name);
+        assert_eq!(
+            "3.00 kilobytes",
+            crate::ByteSizeFormatter::format_auto_with(3000, System::Decimal, options)
+        );
+    }
+
+    #[test]
+    fn formatting() {
+        use crate::options::{FormatOptions, UnitStyle};
+        use crate::ByteSizeFormatter;
+
+        let options = FormatOptions::new()
+            .locale(&Locale::RU)
+            .unit_style(UnitStyle::Name)
+            .trim_trailing_zeros(true);
+        let format =
+            |value| ByteSizeFormatter::format_auto_i64_with(value, System::Decimal, options);
+        assert_eq!("1,5 мегабайта", format(1_500_000));
+        assert_eq!("-21 килобайт", format(-21_000));
+        assert_eq!("0 байт", format(0));
+        assert_eq!("12 байт", format(12));
+
+        let parts = ByteSizeFormatter::new(System::Binary, Magnitude::Kilo)
+            .with_options(FormatOptions::new().locale(&Locale::FR))
+            .parts(1536);
+        assert_eq!((',', "Kio"), (parts.get_decimal_point(), parts.get_unit()));
+        assert_eq!("1,50 Kio", parts.to_string());
+        assert_eq!(
+            "Some(Locale { symbols: [\"o\", \"Ko\", \"Mo\", \"Go\", \"To\", \"Po\", \"Eo\", \"Zo\", \"Yo\", \"Ro\", \"Qo\"], decimal_point: ',', .. })",
+            format!("{:?}", Locale::from_tag("fr"))
+        );
+    }
+}
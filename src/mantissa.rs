@@ -0,0 +1,357 @@
+use core::fmt;
+
+use crate::options::{RoundingMode, MAX_PRECISION};
+
+/// Denominators handed to [`Mantissa`] stay below this bound.
+const DENOMINATOR_LIMIT: u128 = 1 << 124;
+
+/// A non-negative value `numerator / denominator` to be formatted. Integers have a denominator of
+/// one, other inputs use it to represent their fractional part.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct Fraction {
+    pub numerator: u128,
+    pub denominator: u128,
+}
+
+impl Fraction {
+    pub fn integer(value: u128) -> Self {
+        Self {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+
+    /// The number of bytes in `bits` bits.
+    pub fn bits(bits: u128) -> Self {
+        Self::integer(bits).over(8)
+    }
+
+    /// Converts the absolute value of a finite `f64` exactly, as long as its fractional bits fit
+    /// into 127 bits. Values beyond `u128::MAX` saturate.
+    pub fn from_f64(value: f64) -> Self {
+        debug_assert!(value.is_finite());
+        let bits = value.abs().to_bits();
+        let exponent = ((bits >> 52) & 0x7ff) as i32;
+        let fraction = (bits & ((1 << 52) - 1)) as u128;
+        let (mut significand, mut exponent) = match exponent {
+            0 => (fraction, -1074),
+            _ => (fraction | 1 << 52, exponent - 1075),
+        };
+        if significand == 0 {
+            return Self::integer(0);
+        }
+
+        if exponent >= 0 {
+            let numerator = match exponent < significand.leading_zeros() as i32 {
+                true => significand << exponent,
+                false => u128::MAX,
+            };
+            return Self::integer(numerator);
+        }
+
+        let trailing_zeros = significand.trailing_zeros().min(exponent.unsigned_abs());
+        significand >>= trailing_zeros;
+        exponent += trailing_zeros as i32;
+        if exponent < -127 {
+            significand = significand
+                .checked_shr((-127 - exponent) as u32)
+                .unwrap_or(0);
+            exponent = -127;
+        }
+        Self {
+            numerator: significand,
+            denominator: 1 << -exponent,
+        }
+    }
+
+    /// Multiplies the value by `factor`, or returns `None` if the product exceeds `u128::MAX`.
+    pub fn checked_times(&self, factor: u128) -> Option<Self> {
+        match self.numerator.checked_mul(factor) {
+            Some(numerator) => Some(Self {
+                numerator,
+                denominator: self.denominator,
+            }),
+            None if self.denominator.is_multiple_of(factor) => Some(Self {
+                numerator: self.numerator,
+                denominator: self.denominator / factor,
+            }),
+            None => None,
+        }
+    }
+
+    /// Divides the value by `factor`, dropping the lowest bits of the denominator if it would
+    /// overflow.
+    pub fn over(&self, factor: u128) -> Self {
+        let mut value = *self;
+        loop {
+            match value.denominator.checked_mul(factor) {
+                Some(denominator) => {
+                    value.denominator = denominator;
+                    return value;
+                }
+                None => {
+                    value.numerator >>= 1;
+                    value.denominator >>= 1;
+                }
+            }
+        }
+    }
+
+    /// The integer part of the value.
+    pub fn floor(&self) -> u128 {
+        self.numerator / self.denominator
+    }
+
+    /// Returns the numerator and denominator of `self / divisor`. The result is exact unless the
+    /// denominator would exceed 2^124, in which case the lowest bits of both are dropped.
+    pub fn divided_by(&self, divisor: u128) -> (u128, u128) {
+        match self.denominator.checked_mul(divisor) {
+            Some(denominator) if denominator < DENOMINATOR_LIMIT => (self.numerator, denominator),
+            _ => {
+                let bits = |value: u128| 128 - value.leading_zeros();
+                let excess = bits(self.denominator) + bits(divisor) - 123;
+                let denominator = (self.denominator >> excess) * divisor;
+                (self.numerator >> excess, denominator)
+            }
+        }
+    }
+}
+
+/// The quotient of two integers, rounded to a fixed number of decimal places using only integer
+/// arithmetic, so every input is rounded exactly.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct Mantissa {
+    pub integer: u128,
+    fraction: [u8; MAX_PRECISION as usize],
+    decimals: u8,
+    pub exact: bool,
+}
+
+impl Mantissa {
+    /// Computes `numerator / denominator` rounded to `decimals` places.
+    ///
+    /// The denominator must be below 2^124 so the remainder can be scaled by ten without overflow.
+    pub fn new(numerator: u128, denominator: u128, decimals: u8, rounding: RoundingMode) -> Self {
+        debug_assert!(denominator != 0 && denominator < DENOMINATOR_LIMIT);
+        let decimals = decimals.min(MAX_PRECISION);
+        let mut mantissa = Self {
+            integer: numerator / denominator,
+            fraction: [0; MAX_PRECISION as usize],
+            decimals,
+            exact: true,
+        };
+
+        let mut remainder = numerator % denominator;
+        for digit in &mut mantissa.fraction[..decimals as usize] {
+            remainder *= 10;
+            *digit = (remainder / denominator) as u8;
+            remainder %= denominator;
+        }
+
+        mantissa.exact = remainder == 0;
+        let round_up = match rounding {
+            RoundingMode::Floor => false,
+            RoundingMode::Ceil => remainder != 0,
+            RoundingMode::HalfEven => match (remainder * 2).cmp(&denominator) {
+                core::cmp::Ordering::Greater => true,
+                core::cmp::Ordering::Equal => mantissa.last_digit() % 2 == 1,
+                core::cmp::Ordering::Less => false,
+            },
+            RoundingMode::HalfUp => remainder * 2 >= denominator,
+        };
+        if round_up {
+            mantissa.increment();
+        }
+        mantissa
+    }
+
+    /// Computes `numerator / denominator` rounded to `figures` significant figures. Digits before
+    /// the decimal point are never dropped.
+    pub fn significant(
+        numerator: u128,
+        denominator: u128,
+        figures: u8,
+        rounding: RoundingMode,
+    ) -> Self {
+        let figures = figures.clamp(1, MAX_PRECISION);
+        let integer = numerator / denominator;
+        let decimals = if integer > 0 {
+            figures.saturating_sub(integer.ilog10() as u8 + 1)
+        } else {
+            // Leading zeros after the decimal point are not significant.
+            let mut remainder = numerator % denominator;
+            let mut leading_zeros = 0;
+            while remainder != 0 && remainder * 10 < denominator && leading_zeros < MAX_PRECISION {
+                remainder *= 10;
+                leading_zeros += 1;
+            }
+            if remainder == 0 {
+                figures - 1
+            } else {
+                (leading_zeros + figures).min(MAX_PRECISION)
+            }
+        };
+
+        let mantissa = Self::new(numerator, denominator, decimals, rounding);
+        // Rounding up may have carried into a new leading digit ("9.995" -> "10.00"), which leaves
+        // one figure too many.
+        if mantissa.significant_digits() > figures && decimals > 0 {
+            return Self::new(numerator, denominator, decimals - 1, rounding);
+        }
+        mantissa
+    }
+
+    pub fn fraction(&self) -> &[u8] {
+        &self.fraction[..self.decimals as usize]
+    }
+
+    /// The fractional digits without trailing zeros.
+    pub fn trimmed_fraction(&self) -> &[u8] {
+        let fraction = self.fraction();
+        let len = fraction
+            .iter()
+            .rposition(|&digit| digit != 0)
+            .map_or(0, |i| i + 1);
+        &fraction[..len]
+    }
+
+    /// The fractional digits that are shown, optionally without trailing zeros.
+    pub fn shown_fraction(&self, trim_trailing_zeros: bool) -> &[u8] {
+        match trim_trailing_zeros {
+            true => self.trimmed_fraction(),
+            false => self.fraction(),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.integer == 0 && self.fraction().iter().all(|&digit| digit == 0)
+    }
+
+    /// Writes the mantissa as `integer[.fraction]` with the given decimal point, optionally
+    /// dropping trailing zeros.
+    pub fn write_to(
+        &self,
+        f: &mut impl fmt::Write,
+        trim_trailing_zeros: bool,
+        decimal_point: char,
+    ) -> fmt::Result {
+        write!(f, "{}", self.integer)?;
+        let fraction = self.shown_fraction(trim_trailing_zeros);
+        if !fraction.is_empty() {
+            f.write_char(decimal_point)?;
+            for &digit in fraction {
+                f.write_char((b'0' + digit) as char)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn last_digit(&self) -> u8 {
+        match self.fraction().last() {
+            Some(&digit) => digit,
+            None => (self.integer % 10) as u8,
+        }
+    }
+
+    fn increment(&mut self) {
+        for digit in self.fraction[..self.decimals as usize].iter_mut().rev() {
+            if *digit == 9 {
+                *digit = 0;
+            } else {
+                *digit += 1;
+                return;
+            }
+        }
+        self.integer += 1;
+    }
+
+    fn significant_digits(&self) -> u8 {
+        if self.integer > 0 {
+            self.integer.ilog10() as u8 + 1 + self.decimals
+        } else {
+            let leading_zeros = self
+                .fraction()
+                .iter()
+                .take_while(|&&digit| digit == 0)
+                .count();
+            self.decimals - leading_zeros as u8
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    fn render(mantissa: Mantissa) -> String {
+        let mut output = String::new();
+        mantissa.write_to(&mut output, false, '.').unwrap();
+        output
+    }
+
+    #[test]
+    fn rounding() {
+        let round = |n, d, decimals, mode| render(Mantissa::new(n, d, decimals, mode));
+        assert_eq!("1.14", round(1145, 1000, 2, RoundingMode::Floor));
+        assert_eq!("1.15", round(1141, 1000, 2, RoundingMode::Ceil));
+        assert_eq!("1.14", round(1145, 1000, 2, RoundingMode::HalfEven));
+        assert_eq!("1.16", round(1155, 1000, 2, RoundingMode::HalfEven));
+        assert_eq!("1.15", round(1145, 1000, 2, RoundingMode::HalfUp));
+        assert_eq!("2", round(3, 2, 0, RoundingMode::HalfEven));
+        assert_eq!("10.00", round(9999, 1000, 2, RoundingMode::HalfUp));
+        assert_eq!("0.001", round(1, 1024, 3, RoundingMode::HalfUp));
+        assert!(Mantissa::new(1536, 1024, 1, RoundingMode::Floor).exact);
+        assert!(!Mantissa::new(1537, 1024, 1, RoundingMode::Floor).exact);
+    }
+
+    #[test]
+    fn fractions() {
+        let fraction = |numerator, denominator| Fraction {
+            numerator,
+            denominator,
+        };
+        assert_eq!(fraction(3, 2), Fraction::from_f64(1.5));
+        assert_eq!(fraction(3, 2), Fraction::from_f64(-1.5));
+        assert_eq!(
+            Fraction::integer(1 << 60),
+            Fraction::from_f64(2f64.powi(60))
+        );
+        assert_eq!(Fraction::integer(0), Fraction::from_f64(0.0));
+        assert_eq!(Fraction::integer(u128::MAX), Fraction::from_f64(f64::MAX));
+        assert_eq!(fraction(1, 1 << 127), Fraction::from_f64(2f64.powi(-127)));
+        assert_eq!(fraction(0, 1 << 127), Fraction::from_f64(f64::MIN_POSITIVE));
+        assert_eq!(0, Fraction::from_f64(0.1).floor());
+
+        assert_eq!(Some(fraction(24, 2)), fraction(3, 2).checked_times(8));
+        assert_eq!(
+            Some(fraction(u128::MAX, 1)),
+            fraction(u128::MAX, 8).checked_times(8)
+        );
+        assert_eq!(None, fraction(u128::MAX, 3).checked_times(8));
+        assert_eq!(fraction(3, 16), fraction(3, 2).over(8));
+        assert_eq!(
+            fraction(1 << 124, 1 << 127),
+            fraction(1 << 125, 1 << 126).over(4)
+        );
+
+        assert_eq!((3, 2048), fraction(3, 2).divided_by(1024));
+        // 2^30 * 2^100 exceeds the limit, so both sides lose their lowest nine bits.
+        assert_eq!(
+            (3 << 21, 1 << 121),
+            fraction(3 << 30, 1 << 30).divided_by(1 << 100)
+        );
+    }
+
+    #[test]
+    fn significant() {
+        let round =
+            |n, d, figures| render(Mantissa::significant(n, d, figures, RoundingMode::HalfEven));
+        assert_eq!("954", round(1_000_000_000, 1 << 20, 3));
+        assert_eq!("1.43", round(1_500_000, 1 << 20, 3));
+        assert_eq!("10.0", round(9999, 1000, 3));
+        assert_eq!("0.0977", round(102_400, 1 << 20, 3));
+        assert_eq!("0.100", round(104_857, 1 << 20, 3));
+        assert_eq!("0.00", round(0, 1024, 3));
+        assert_eq!("1024", round(1 << 30, 1 << 20, 3));
+    }
+}
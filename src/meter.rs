@@ -0,0 +1,259 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::time::Duration;
+
+#[cfg(feature = "alloc")]
+use crate::rate::RateFormatter;
+
+/// The largest number of intervals a [`Smoothing::Window`] can average over.
+pub const MAX_WINDOW: usize = 32;
+
+/// The width of a cumulative counter, which decides where it wraps around.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum CounterWidth {
+    /// Wraps after `u32::MAX`, like many kernel and SNMP counters on 32-bit systems.
+    Bits32,
+    #[default]
+    Bits64,
+}
+
+impl CounterWidth {
+    pub fn max(&self) -> u64 {
+        match self {
+            CounterWidth::Bits32 => u32::MAX as u64,
+            CounterWidth::Bits64 => u64::MAX,
+        }
+    }
+}
+
+/// How successive rate measurements are combined.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Smoothing {
+    /// The rate of the most recent interval only.
+    #[default]
+    None,
+    /// An exponentially weighted moving average. Each interval of length `dt` is weighted with
+    /// `dt / (time_constant + dt)`, so irregular sampling is accounted for.
+    Ewma { time_constant: Duration },
+    /// The average rate over the most recent intervals, at most [`MAX_WINDOW`].
+    Window(usize),
+}
+
+/// Derives a throughput from samples of a monotonically increasing counter, such as the byte
+/// totals in `/proc/net/dev` or `/proc/diskstats`.
+///
+/// Samples are `(timestamp, total)` pairs. Timestamps are durations since an arbitrary but fixed
+/// origin, e.g. [`Instant::elapsed`](std::time::Instant::elapsed) of a start time. A total below
+/// the previous one is treated as a wraparound if the counter was in the upper half of its
+/// range, and as a reset otherwise; after a reset the meter starts measuring again from the new
+/// total, keeping the last rate.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+///
+/// use bittenhumans::consts::System;
+/// use bittenhumans::meter::{CounterWidth, RateMeter};
+/// use bittenhumans::rate::RateFormatter;
+///
+/// let mut meter = RateMeter::new(CounterWidth::Bits32);
+/// meter.update(Duration::from_secs(0), 4_294_000_000);
+/// // The counter wrapped around between these samples.
+/// meter.update(Duration::from_secs(1), 1_048_000);
+///
+/// let formatter = RateFormatter::new(System::Binary);
+/// assert_eq!(Some("1.92 MiB/s".to_string()), meter.format(&formatter));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateMeter {
+    width: CounterWidth,
+    smoothing: Smoothing,
+    last: Option<(Duration, u64)>,
+    rate: Option<f64>,
+    /// Recent `(bytes, elapsed)` intervals for [`Smoothing::Window`], a ring buffer ending before
+    /// `next`.
+    window: [(u64, Duration); MAX_WINDOW],
+    window_len: usize,
+    next: usize,
+}
+
+impl RateMeter {
+    /// Creates a meter for a counter of the given width, without smoothing.
+    pub fn new(width: CounterWidth) -> Self {
+        Self {
+            width,
+            smoothing: Smoothing::None,
+            last: None,
+            rate: None,
+            window: [(0, Duration::ZERO); MAX_WINDOW],
+            window_len: 0,
+            next: 0,
+        }
+    }
+
+    /// Sets how successive measurements are combined.
+    pub fn with_smoothing(mut self, smoothing: Smoothing) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+
+    pub fn get_width(&self) -> CounterWidth {
+        self.width
+    }
+
+    pub fn get_smoothing(&self) -> Smoothing {
+        self.smoothing
+    }
+
+    /// Records a sample of the cumulative counter and returns the updated rate in bytes per
+    /// second, if at least one interval has been measured. Samples whose timestamp does not
+    /// advance are ignored. Samples wider than the counter are truncated to its width, as the
+    /// counter itself would have wrapped.
+    pub fn update(&mut self, timestamp: Duration, total: u64) -> Option<f64> {
+        let total = total & self.width.max();
+        let Some((last_timestamp, last_total)) = self.last else {
+            self.last = Some((timestamp, total));
+            return self.rate;
+        };
+        if timestamp <= last_timestamp {
+            return self.rate;
+        }
+        self.last = Some((timestamp, total));
+
+        let bytes = if total >= last_total {
+            total - last_total
+        } else if last_total > self.width.max() / 2 {
+            (self.width.max() - last_total) + total + 1
+        } else {
+            self.window_len = 0;
+            return self.rate;
+        };
+        let elapsed = timestamp - last_timestamp;
+        let instant = bytes as f64 / elapsed.as_secs_f64();
+
+        self.rate = Some(match (self.smoothing, self.rate) {
+            (Smoothing::Ewma { time_constant }, Some(rate)) => {
+                let dt = elapsed.as_secs_f64();
+                let weight = dt / (time_constant.as_secs_f64() + dt);
+                rate + weight * (instant - rate)
+            }
+            (Smoothing::Window(size), _) => self.push_window(bytes, elapsed, size),
+            _ => instant,
+        });
+        self.rate
+    }
+
+    /// The current rate in bytes per second, or `None` before the first interval.
+    pub fn rate(&self) -> Option<f64> {
+        self.rate
+    }
+
+    /// Formats the current rate, or returns `None` before the first interval.
+    #[cfg(feature = "alloc")]
+    pub fn format(&self, formatter: &RateFormatter) -> Option<String> {
+        self.rate.map(|rate| formatter.format_per_second(rate))
+    }
+
+    /// Forgets all samples and the current rate.
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.rate = None;
+        self.window_len = 0;
+    }
+
+    /// Adds an interval to the window and returns the average rate over it.
+    fn push_window(&mut self, bytes: u64, elapsed: Duration, size: usize) -> f64 {
+        let size = size.clamp(1, MAX_WINDOW);
+        self.window[self.next] = (bytes, elapsed);
+        self.next = (self.next + 1) % MAX_WINDOW;
+        self.window_len = (self.window_len + 1).min(size);
+        let start = (self.next + MAX_WINDOW - self.window_len) % MAX_WINDOW;
+
+        let (bytes, elapsed) = (0..self.window_len)
+            .map(|i| self.window[(start + i) % MAX_WINDOW])
+            .fold((0_u128, Duration::ZERO), |(bytes, elapsed), sample| {
+                (bytes + sample.0 as u128, elapsed + sample.1)
+            });
+        bytes as f64 / elapsed.as_secs_f64()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn counters() {
+        let mut meter = RateMeter::new(CounterWidth::Bits64);
+        assert_eq!(None, meter.update(secs(10), 1000));
+        assert_eq!(Some(500.0), meter.update(secs(12), 2000));
+        // Time going backwards or standing still is ignored.
+        assert_eq!(Some(500.0), meter.update(secs(12), 9000));
+        assert_eq!(Some(100.0), meter.update(secs(22), 3000));
+
+        // A reset keeps the last rate and measures from the new total.
+        assert_eq!(Some(100.0), meter.update(secs(23), 10));
+        assert_eq!(Some(40.0), meter.update(secs(24), 50));
+
+        meter.reset();
+        assert_eq!(None, meter.rate());
+        assert_eq!(None, meter.update(secs(30), 0));
+    }
+
+    #[test]
+    fn wraparound() {
+        let mut meter = RateMeter::new(CounterWidth::Bits32);
+        meter.update(secs(0), u32::MAX as u64 - 99);
+        assert_eq!(Some(200.0), meter.update(secs(1), 100));
+
+        let mut meter = RateMeter::new(CounterWidth::Bits64);
+        meter.update(secs(0), u64::MAX);
+        assert_eq!(Some(1.0), meter.update(secs(1), 0));
+
+        // A 64-bit counter in the lower half of its range cannot have wrapped.
+        let mut meter = RateMeter::new(CounterWidth::Bits64);
+        meter.update(secs(0), u32::MAX as u64);
+        assert_eq!(None, meter.update(secs(1), 100));
+
+        // Samples wider than a 32-bit counter are truncated rather than underflowing.
+        let mut meter = RateMeter::new(CounterWidth::Bits32);
+        meter.update(secs(0), 5_000_000_000);
+        assert_eq!(None, meter.update(secs(1), 10));
+        let mut meter = RateMeter::new(CounterWidth::Bits32);
+        meter.update(secs(0), (1 << 32) - 10);
+        assert_eq!(Some(20.0), meter.update(secs(1), (1 << 33) + 10));
+    }
+
+    #[test]
+    fn ewma() {
+        let time_constant = secs(1);
+        let mut meter =
+            RateMeter::new(CounterWidth::Bits64).with_smoothing(Smoothing::Ewma { time_constant });
+        meter.update(secs(0), 0);
+        assert_eq!(Some(100.0), meter.update(secs(1), 100));
+        // Weighted by 1 / (1 + 1).
+        assert_eq!(Some(200.0), meter.update(secs(2), 400));
+        // Weighted by 3 / (1 + 3).
+        assert_eq!(Some(50.0), meter.update(secs(5), 400));
+    }
+
+    #[test]
+    fn window() {
+        let mut meter = RateMeter::new(CounterWidth::Bits64).with_smoothing(Smoothing::Window(3));
+        meter.update(secs(0), 0);
+        assert_eq!(Some(100.0), meter.update(secs(1), 100));
+        assert_eq!(Some(200.0), meter.update(secs(2), 400));
+        assert_eq!(Some(200.0), meter.update(secs(4), 800));
+        // The first interval drops out: (300 + 400 + 600) / 4.
+        assert_eq!(Some(325.0), meter.update(secs(5), 1400));
+
+        for i in 0..100 {
+            meter.update(secs(6 + i), 1400 + 10 * (i + 1));
+        }
+        assert_eq!(Some(10.0), meter.rate());
+    }
+}
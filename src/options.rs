@@ -0,0 +1,396 @@
+use crate::locale::Locale;
+
+/// What to put between the number and the unit.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Separator {
+    /// `"1.50MB"`
+    None,
+    /// `"1.50 MB"`
+    #[default]
+    Space,
+    /// `"1.50\u{202F}MB"`, the narrow no-break space recommended by the SI brochure.
+    NarrowNoBreakSpace,
+}
+
+impl Separator {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Separator::None => "",
+            Separator::Space => " ",
+            Separator::NarrowNoBreakSpace => "\u{202F}",
+        }
+    }
+}
+
+/// The largest number of decimal places or significant figures a formatter will show.
+pub const MAX_PRECISION: u8 = 32;
+
+/// How many digits of the mantissa are shown.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Precision {
+    /// A fixed number of decimal places: `"953.67 MiB"`, `"1.43 MiB"`.
+    Decimals(u8),
+    /// A fixed number of significant figures: `"954 MiB"`, `"1.43 MiB"`, `"12.3 GiB"`.
+    ///
+    /// Digits before the decimal point are never dropped, so mantissas with more integer digits
+    /// than requested figures are shown rounded to a whole number. Zero is treated as one figure.
+    Significant(u8),
+}
+
+/// How the mantissa is rounded to the displayed precision.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum RoundingMode {
+    /// Round towards negative infinity, never overstating a value (e.g. free disk space).
+    /// Negative values are therefore rounded away from zero.
+    Floor,
+    /// Round towards positive infinity, never understating a value (e.g. quota usage).
+    /// Negative values are therefore rounded towards zero.
+    Ceil,
+    /// Round to nearest, ties to the even digit ("banker's rounding").
+    #[default]
+    HalfEven,
+    /// Round to nearest, ties away from zero.
+    HalfUp,
+}
+
+impl RoundingMode {
+    /// The mode to apply to the absolute value of a negative number so that the signed result
+    /// is rounded as documented.
+    pub(crate) fn mirrored(&self) -> Self {
+        match self {
+            RoundingMode::Floor => RoundingMode::Ceil,
+            RoundingMode::Ceil => RoundingMode::Floor,
+            mode => *mode,
+        }
+    }
+}
+
+/// When signed values get an explicit sign.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum SignPolicy {
+    /// `"-512 KiB"`, `"1.20 GiB"`
+    #[default]
+    NegativeOnly,
+    /// `"-512 KiB"`, `"+1.20 GiB"`
+    Always,
+}
+
+/// The character used for negative values.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum MinusSign {
+    /// The ASCII hyphen-minus `-`.
+    #[default]
+    Hyphen,
+    /// The typographic minus sign `\u{2212}`.
+    Unicode,
+}
+
+impl MinusSign {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            MinusSign::Hyphen => "-",
+            MinusSign::Unicode => "\u{2212}",
+        }
+    }
+}
+
+/// How bit units are written.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum BitSymbol {
+    /// `"Mb"`, `"Mib"`
+    #[default]
+    Short,
+    /// `"Mbit"`, `"Mibit"`
+    Long,
+}
+
+/// The quantity values are shown in.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Units {
+    /// `"MB"`, `"MiB"`
+    #[default]
+    Bytes,
+    /// Bits, as used for link speeds and bandwidth caps. Byte counts are multiplied by eight;
+    /// plain bits beyond `u128::MAX` saturate and are reported as
+    /// [inexact](crate::parts::SizeParts::is_exact).
+    Bits(BitSymbol),
+}
+
+impl Units {
+    /// The unit symbol that follows the prefix and infix.
+    pub const fn symbol(&self) -> &'static str {
+        match self {
+            Units::Bytes => "B",
+            Units::Bits(BitSymbol::Short) => "b",
+            Units::Bits(BitSymbol::Long) => "bit",
+        }
+    }
+}
+
+/// Whether units are written as symbols or spelled out.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum UnitStyle {
+    /// `"1.50 MB"`, `"1.43 MiB"`
+    #[default]
+    Symbol,
+    /// `"1.50 megabytes"`, `"1.43 mebibytes"`, for UI copy and screen readers. JEDEC units use the
+    /// decimal names, and bit units are spelled out regardless of their [`BitSymbol`].
+    ///
+    /// The plural form follows the rules of the [`Locale`] for the number as shown. In English,
+    /// the singular is used only when the number is exactly `1`, so `"1 kibibyte"` and
+    /// `"-1 byte"`, but `"1.00 kibibytes"` and `"0 bytes"`.
+    Name,
+}
+
+/// Controls how a [`ByteSizeFormatter`](crate::ByteSizeFormatter) renders values.
+///
+/// The defaults reproduce the classic `"1.43 MiB"` output.
+///
+/// # Example
+/// ```
+/// use bittenhumans::ByteSizeFormatter;
+/// use bittenhumans::consts::System;
+/// use bittenhumans::options::{FormatOptions, Separator};
+///
+/// let options = FormatOptions::new()
+///     .precision(3)
+///     .separator(Separator::None)
+///     .trim_trailing_zeros(true);
+///
+/// assert_eq!("1.5MB", ByteSizeFormatter::format_auto_with(1_500_000, System::Decimal, options));
+/// assert_eq!("1.431MiB", ByteSizeFormatter::format_auto_with(1_500_000, System::Binary, options));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FormatOptions {
+    precision: Precision,
+    separator: Separator,
+    trim_trailing_zeros: bool,
+    rounding: RoundingMode,
+    sign: SignPolicy,
+    minus_sign: MinusSign,
+    units: Units,
+    unit_style: UnitStyle,
+    locale: &'static Locale,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FormatOptions {
+    /// Creates the default options: two decimals rounded half to even, a space separator, no
+    /// trimming and English units.
+    pub const fn new() -> Self {
+        Self {
+            precision: Precision::Decimals(2),
+            separator: Separator::Space,
+            trim_trailing_zeros: false,
+            rounding: RoundingMode::HalfEven,
+            sign: SignPolicy::NegativeOnly,
+            minus_sign: MinusSign::Hyphen,
+            units: Units::Bytes,
+            unit_style: UnitStyle::Symbol,
+            locale: &Locale::EN,
+        }
+    }
+
+    /// Sets the number of decimal places, at most [`MAX_PRECISION`]. Plain byte values are always
+    /// shown without decimals.
+    pub const fn precision(mut self, precision: u8) -> Self {
+        let precision = if precision > MAX_PRECISION {
+            MAX_PRECISION
+        } else {
+            precision
+        };
+        self.precision = Precision::Decimals(precision);
+        self
+    }
+
+    /// Sets the number of significant figures, keeping the width of the mantissa stable regardless
+    /// of its size. At most [`MAX_PRECISION`] figures are shown, and plain byte values are always
+    /// shown without decimals.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::System;
+    /// use bittenhumans::options::FormatOptions;
+    ///
+    /// let options = FormatOptions::new().significant_figures(3);
+    /// let format = |value| ByteSizeFormatter::format_auto_with(value, System::Binary, options);
+    /// assert_eq!("954 MiB", format(1_000_000_000));
+    /// assert_eq!("1.43 MiB", format(1_500_000));
+    /// assert_eq!("12.3 GiB", format(13_200_000_000));
+    /// ```
+    pub const fn significant_figures(mut self, figures: u8) -> Self {
+        let figures = if figures > MAX_PRECISION {
+            MAX_PRECISION
+        } else {
+            figures
+        };
+        self.precision = Precision::Significant(figures);
+        self
+    }
+
+    /// Sets the separator between the number and the unit.
+    pub const fn separator(mut self, separator: Separator) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Removes trailing zeros after the decimal point, and the point itself if nothing remains
+    /// (`"1.50 MB"` becomes `"1.5 MB"`, `"2.00 GiB"` becomes `"2 GiB"`).
+    pub const fn trim_trailing_zeros(mut self, trim: bool) -> Self {
+        self.trim_trailing_zeros = trim;
+        self
+    }
+
+    /// Sets how the mantissa is rounded to the displayed precision. The rounding also decides
+    /// which magnitude [`fit`](crate::ByteSizeFormatter::fit) picks.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::System;
+    /// use bittenhumans::options::{FormatOptions, RoundingMode};
+    ///
+    /// let format = |rounding| {
+    ///     let options = FormatOptions::new().precision(1).rounding(rounding);
+    ///     ByteSizeFormatter::format_auto_with(1_250_000, System::Decimal, options)
+    /// };
+    /// assert_eq!("1.2 MB", format(RoundingMode::Floor));
+    /// assert_eq!("1.3 MB", format(RoundingMode::Ceil));
+    /// assert_eq!("1.2 MB", format(RoundingMode::HalfEven));
+    /// assert_eq!("1.3 MB", format(RoundingMode::HalfUp));
+    /// ```
+    pub const fn rounding(mut self, rounding: RoundingMode) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Sets when signed values are prefixed with a sign. Values that display as zero never get
+    /// one.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::System;
+    /// use bittenhumans::options::{FormatOptions, MinusSign, SignPolicy};
+    ///
+    /// let options = FormatOptions::new()
+    ///     .sign(SignPolicy::Always)
+    ///     .minus_sign(MinusSign::Unicode);
+    /// let format = |delta| ByteSizeFormatter::format_auto_i64_with(delta, System::Binary, options);
+    /// assert_eq!("+1.20 GiB", format(1_288_490_189));
+    /// assert_eq!("\u{2212}512 B", format(-512));
+    /// assert_eq!("0 B", format(0));
+    /// ```
+    pub const fn sign(mut self, sign: SignPolicy) -> Self {
+        self.sign = sign;
+        self
+    }
+
+    /// Sets the character used for negative values.
+    pub const fn minus_sign(mut self, minus_sign: MinusSign) -> Self {
+        self.minus_sign = minus_sign;
+        self
+    }
+
+    /// Sets whether values are shown in bytes or bits.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::System;
+    /// use bittenhumans::options::{BitSymbol, FormatOptions, Units};
+    ///
+    /// let short = FormatOptions::new().units(Units::Bits(BitSymbol::Short));
+    /// let long = FormatOptions::new().units(Units::Bits(BitSymbol::Long));
+    /// assert_eq!("12.00 Mb", ByteSizeFormatter::format_auto_with(1_500_000, System::Decimal, short));
+    /// assert_eq!("11.44 Mibit", ByteSizeFormatter::format_auto_with(1_500_000, System::Binary, long));
+    /// ```
+    pub const fn units(mut self, units: Units) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// Sets whether units are written as symbols or spelled out.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::System;
+    /// use bittenhumans::options::{FormatOptions, UnitStyle};
+    ///
+    /// let options = FormatOptions::new()
+    ///     .unit_style(UnitStyle::Name)
+    ///     .trim_trailing_zeros(true);
+    /// let format = |value, system| ByteSizeFormatter::format_auto_with(value, system, options);
+    /// assert_eq!("1.5 megabytes", format(1_500_000, System::Decimal));
+    /// assert_eq!("1 kibibyte", format(1024, System::Binary));
+    /// assert_eq!("1 byte", format(1, System::Binary));
+    /// ```
+    pub const fn unit_style(mut self, unit_style: UnitStyle) -> Self {
+        self.unit_style = unit_style;
+        self
+    }
+
+    /// Sets the language of the unit symbols and names and the decimal point.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSizeFormatter;
+    /// use bittenhumans::consts::System;
+    /// use bittenhumans::locale::Locale;
+    /// use bittenhumans::options::{FormatOptions, UnitStyle};
+    ///
+    /// let options = FormatOptions::new()
+    ///     .locale(&Locale::FR)
+    ///     .unit_style(UnitStyle::Name)
+    ///     .precision(1);
+    /// assert_eq!("1,5 mégaoctet", ByteSizeFormatter::format_auto_with(1_500_000, System::Decimal, options));
+    /// assert_eq!("2,0 mégaoctets", ByteSizeFormatter::format_auto_with(2_000_000, System::Decimal, options));
+    /// ```
+    pub const fn locale(mut self, locale: &'static Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    pub const fn get_precision(&self) -> Precision {
+        self.precision
+    }
+
+    pub const fn get_separator(&self) -> Separator {
+        self.separator
+    }
+
+    pub const fn get_trim_trailing_zeros(&self) -> bool {
+        self.trim_trailing_zeros
+    }
+
+    pub const fn get_rounding(&self) -> RoundingMode {
+        self.rounding
+    }
+
+    pub const fn get_sign(&self) -> SignPolicy {
+        self.sign
+    }
+
+    pub const fn get_minus_sign(&self) -> MinusSign {
+        self.minus_sign
+    }
+
+    pub const fn get_units(&self) -> Units {
+        self.units
+    }
+
+    pub const fn get_unit_style(&self) -> UnitStyle {
+        self.unit_style
+    }
+
+    pub const fn get_locale(&self) -> &'static Locale {
+        self.locale
+    }
+}
@@ -0,0 +1,542 @@
+use core::fmt;
+
+use crate::consts::*;
+use crate::ByteSizeFormatter;
+
+/// The reason a size string could not be parsed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ParseErrorKind {
+    /// The input contained no number.
+    Empty,
+    /// A digit was expected but something else was found.
+    InvalidNumber,
+    /// The unit suffix is not one of the units this crate knows about.
+    UnknownUnit,
+    /// The resulting byte count does not fit into the requested integer type, or the number has
+    /// more fractional digits than can be represented (38).
+    Overflow,
+}
+
+/// An error returned when parsing a size string fails.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    position: usize,
+}
+
+impl ParseError {
+    const fn new(kind: ParseErrorKind, position: usize) -> Self {
+        Self { kind, position }
+    }
+
+    pub const fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// Byte offset into the input at which the problem was detected.
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self.kind {
+            ParseErrorKind::Empty => "missing number",
+            ParseErrorKind::InvalidNumber => "invalid number",
+            ParseErrorKind::UnknownUnit => "unknown unit",
+            ParseErrorKind::Overflow => "size too large",
+        };
+        write!(f, "{reason} at position {}", self.position)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Parses a human-readable size such as `"512MiB"`, `"1.5 GB"` or `"10k"` into a byte count.
+///
+/// Every byte unit emitted by [`ByteSizeFormatter`] is understood. Prefixes without the `i` infix
+/// (`K`, `MB`, ...) are decimal, prefixes with it (`Ki`, `MiB`, ...) are binary; use
+/// [`parse_size_in`] to read the former as JEDEC units instead. Prefix letters and the infix are
+/// case-insensitive (`"1 kiB"` and `"1 GIB"` work), the trailing `B` is optional but has to be
+/// upper-case. A lower-case `b` or the word `bit` (`"Mb"`, `"Mbit"`, `"Kibit"`) denotes bits,
+/// which are rejected as [`UnknownUnit`](ParseErrorKind::UnknownUnit) rather than silently read
+/// as an eighth of the size; use [`parse_bits`] for those. Fractional results are rounded to the
+/// nearest byte, ties to even.
+///
+/// # Arguments
+///
+/// * `input` - The string to parse
+///
+/// Like all parsing functions this is a `const fn`; see [`bytes!`](crate::bytes) for size
+/// literals checked at compile time.
+///
+/// # Example
+/// ```
+/// use bittenhumans::parse::{parse_size, ParseErrorKind};
+///
+/// assert_eq!(Ok(512 * 1024 * 1024), parse_size("512MiB"));
+/// assert_eq!(Ok(1_500_000_000), parse_size("1.5 GB"));
+/// assert_eq!(Ok(10_000), parse_size("10k"));
+///
+/// let err = parse_size("12 XB").unwrap_err();
+/// assert_eq!(ParseErrorKind::UnknownUnit, err.kind());
+/// assert_eq!(3, err.position());
+///
+/// // "mb" would be megabits, which is most likely a typo for megabytes.
+/// assert_eq!(ParseErrorKind::UnknownUnit, parse_size("10mb").unwrap_err().kind());
+/// ```
+///
+/// # Returns
+///
+/// The number of bytes, or a [`ParseError`] describing what went wrong and where
+pub const fn parse_size(input: &str) -> Result<u64, ParseError> {
+    parse_size_in(input, System::Decimal)
+}
+
+/// Like [`parse_size`], but prefixes without the `i` infix are interpreted according to
+/// `system`: powers of 1000 for [`System::Decimal`], powers of 1024 for [`System::Jedec`] and
+/// [`System::Binary`]. Infixed prefixes are always binary.
+///
+/// Decimal and JEDEC units share their labels, so the caller has to know which convention the
+/// input follows.
+///
+/// # Example
+/// ```
+/// use bittenhumans::consts::System;
+/// use bittenhumans::parse::parse_size_in;
+///
+/// assert_eq!(Ok(16 * 1024 * 1024 * 1024), parse_size_in("16 GB", System::Jedec));
+/// assert_eq!(Ok(16_000_000_000), parse_size_in("16 GB", System::Decimal));
+/// assert_eq!(Ok(2048), parse_size_in("2 KiB", System::Decimal));
+/// ```
+pub const fn parse_size_in(input: &str, system: System) -> Result<u64, ParseError> {
+    to_u64(input, parse_size_u128_in(input, system))
+}
+
+/// Like [`parse_size`], but for sizes that may exceed a `u64`.
+///
+/// # Example
+/// ```
+/// use bittenhumans::parse::parse_size_u128;
+///
+/// assert_eq!(Ok(2 * 10_u128.pow(27)), parse_size_u128("2 RB"));
+/// assert_eq!(Ok(1 << 100), parse_size_u128("1QiB"));
+/// ```
+pub const fn parse_size_u128(input: &str) -> Result<u128, ParseError> {
+    parse_size_u128_in(input, System::Decimal)
+}
+
+/// Like [`parse_size_in`], but for sizes that may exceed a `u64`.
+pub const fn parse_size_u128_in(input: &str, system: System) -> Result<u128, ParseError> {
+    parse(input, system, false)
+}
+
+/// Parses a human-readable size into a bit count, e.g. a link speed like `"100 Mbit"`.
+///
+/// Units follow [`parse_size`], except that bit units (a lower-case `b` or the word `bit`) are
+/// accepted, the quantity is returned in bits and byte units are multiplied by eight.
+///
+/// # Example
+/// ```
+/// use bittenhumans::parse::parse_bits;
+///
+/// assert_eq!(Ok(100_000_000), parse_bits("100 Mbit"));
+/// assert_eq!(Ok(8_000_000), parse_bits("1 MB"));
+/// assert_eq!(Ok(1 << 30), parse_bits("1Gib"));
+/// ```
+pub const fn parse_bits(input: &str) -> Result<u64, ParseError> {
+    to_u64(input, parse(input, System::Decimal, true))
+}
+
+/// Converts a size literal into a `u64` byte count at compile time, following [`parse_size`] or,
+/// with a numeral system as second argument, [`parse_size_in`]. Invalid literals and sizes beyond
+/// `u64::MAX` are compile errors.
+///
+/// # Example
+/// ```
+/// use bittenhumans::bytes;
+/// use bittenhumans::consts::System;
+///
+/// const MAX_UPLOAD: u64 = bytes!("1.5 GiB");
+/// assert_eq!(1_610_612_736, MAX_UPLOAD);
+/// assert_eq!(4_000_000, bytes!("4MB"));
+/// assert_eq!(4 << 20, bytes!("4MB", System::Jedec));
+/// ```
+///
+/// ```compile_fail
+/// let size = bittenhumans::bytes!("12 XB");
+/// ```
+///
+/// ```compile_fail
+/// let size = bittenhumans::bytes!("20 EiB");
+/// ```
+#[macro_export]
+macro_rules! bytes {
+    ($input:expr) => {
+        $crate::bytes!($input, $crate::consts::System::Decimal)
+    };
+    ($input:expr, $system:expr) => {{
+        const BYTES: u64 =
+            $crate::parse::expect_size($crate::parse::parse_size_in($input, $system));
+        BYTES
+    }};
+}
+
+/// Narrows a parsed quantity to a `u64`, reporting overflow at the start of the number.
+const fn to_u64(input: &str, parsed: Result<u128, ParseError>) -> Result<u64, ParseError> {
+    match parsed {
+        Ok(value) if value <= u64::MAX as u128 => Ok(value as u64),
+        Ok(_) => {
+            // The number always starts at the first non-whitespace byte.
+            let number_start = skip_whitespace(input.as_bytes(), 0);
+            Err(ParseError::new(ParseErrorKind::Overflow, number_start))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Unwraps a size parsed at compile time, failing compilation with a message describing the
+/// error. Used by [`bytes!`](crate::bytes).
+#[doc(hidden)]
+pub const fn expect_size(parsed: Result<u64, ParseError>) -> u64 {
+    match parsed {
+        Ok(size) => size,
+        Err(err) => match err.kind {
+            ParseErrorKind::Empty => panic!("invalid size literal: missing number"),
+            ParseErrorKind::InvalidNumber => panic!("invalid size literal: invalid number"),
+            ParseErrorKind::UnknownUnit => panic!("invalid size literal: unknown unit"),
+            ParseErrorKind::Overflow => panic!("invalid size literal: size too large"),
+        },
+    }
+}
+
+/// Parses `input` into a byte count, or a bit count if `bits` is set.
+const fn parse(input: &str, system: System, bits: bool) -> Result<u128, ParseError> {
+    let bytes = input.as_bytes();
+    let mut pos = skip_whitespace(bytes, 0);
+    let overflow = ParseError::new(ParseErrorKind::Overflow, pos);
+
+    // The integer and fractional digits are accumulated separately, the latter as a numerator over
+    // a power of ten, so that only the integer part can make the result overflow.
+    let mut integer: u128 = 0;
+    let mut fraction: u128 = 0;
+    let mut scale: u128 = 1;
+    let mut digits = 0;
+    while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+        integer = match push_digit(integer, bytes[pos]) {
+            Some(integer) => integer,
+            None => return Err(overflow),
+        };
+        digits += 1;
+        pos += 1;
+    }
+    if pos < bytes.len() && bytes[pos] == b'.' {
+        pos += 1;
+        let fraction_start = pos;
+        while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+            // Excess fractional digits are reported as overflow rather than silently dropped.
+            (fraction, scale) = match (push_digit(fraction, bytes[pos]), scale.checked_mul(10)) {
+                (Some(fraction), Some(scale)) => (fraction, scale),
+                _ => return Err(overflow),
+            };
+            pos += 1;
+        }
+        if pos == fraction_start {
+            return Err(ParseError::new(ParseErrorKind::InvalidNumber, pos));
+        }
+    } else if digits == 0 {
+        let kind = if pos == bytes.len() {
+            ParseErrorKind::Empty
+        } else {
+            ParseErrorKind::InvalidNumber
+        };
+        return Err(ParseError::new(kind, pos));
+    }
+
+    pos = skip_whitespace(bytes, pos);
+    let unit_start = pos;
+    let Some((system, magnitude, unit_bits, unit_end)) = parse_unit(bytes, pos, system) else {
+        return Err(ParseError::new(ParseErrorKind::UnknownUnit, unit_start));
+    };
+    pos = skip_whitespace(bytes, unit_end);
+    if pos != bytes.len() || (unit_bits && !bits) {
+        return Err(ParseError::new(ParseErrorKind::UnknownUnit, unit_start));
+    }
+
+    let divisor = match magnitude {
+        Some(magnitude) => ByteSizeFormatter::compute_divisor(system, magnitude),
+        None => 1,
+    };
+    let divisor = match (unit_bits, bits) {
+        (false, true) => divisor * 8,
+        _ => divisor,
+    };
+    let (quotient, remainder) = mul_div(fraction, divisor, scale);
+    let Some(whole) = integer.checked_mul(divisor) else {
+        return Err(overflow);
+    };
+    let Some(total) = whole.checked_add(quotient) else {
+        return Err(overflow);
+    };
+    // Rounds to the nearest byte, ties to even.
+    let round_up =
+        remainder > scale - remainder || (remainder == scale - remainder && total & 1 == 1);
+    match total.checked_add(round_up as u128) {
+        Some(total) => Ok(total),
+        None => Err(overflow),
+    }
+}
+
+/// Computes the quotient and remainder of `a * b / c` for `a < c <= 10^38` without overflowing.
+const fn mul_div(a: u128, b: u128, c: u128) -> (u128, u128) {
+    // Long multiplication by the bits of `b`, keeping the remainder modulo `c` below `3 * c`.
+    let (mut quotient, mut remainder) = (0_u128, 0_u128);
+    let mut bit = 128;
+    while bit > 0 {
+        bit -= 1;
+        quotient <<= 1;
+        remainder <<= 1;
+        if (b >> bit) & 1 == 1 {
+            remainder += a;
+        }
+        while remainder >= c {
+            remainder -= c;
+            quotient += 1;
+        }
+    }
+    (quotient, remainder)
+}
+
+const fn push_digit(mantissa: u128, digit: u8) -> Option<u128> {
+    match mantissa.checked_mul(10) {
+        Some(mantissa) => mantissa.checked_add((digit - b'0') as u128),
+        None => None,
+    }
+}
+
+/// Whether `bytes` continues with `prefix` at `pos`.
+const fn starts_with(bytes: &[u8], pos: usize, prefix: &[u8]) -> bool {
+    if bytes.len() - pos < prefix.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < prefix.len() {
+        if bytes[pos + i] != prefix[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Skips ASCII whitespace as well as the no-break spaces a formatter may emit as separator.
+const fn skip_whitespace(bytes: &[u8], mut pos: usize) -> usize {
+    loop {
+        if pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        } else if starts_with(bytes, pos, "\u{202F}".as_bytes()) {
+            pos += "\u{202F}".len();
+        } else if starts_with(bytes, pos, "\u{A0}".as_bytes()) {
+            pos += "\u{A0}".len();
+        } else {
+            return pos;
+        }
+    }
+}
+
+/// Recognizes an optional unit starting at `pos`, returning its system, magnitude (`None` without
+/// prefix), whether it counts bits and the position right after it. Un-infixed prefixes belong to
+/// `unprefixed`.
+const fn parse_unit(
+    bytes: &[u8],
+    mut pos: usize,
+    unprefixed: System,
+) -> Option<(System, Option<Magnitude>, bool, usize)> {
+    let mut magnitude = None;
+    if pos < bytes.len() {
+        let prefix = bytes[pos].to_ascii_uppercase();
+        let mut index = 0;
+        while index < MAGNITUDE_PREFIXES.len() {
+            if MAGNITUDE_PREFIXES[index].as_bytes()[0] == prefix {
+                magnitude = Magnitude::from_exponent(index as u8 + 1);
+                break;
+            }
+            index += 1;
+        }
+    }
+
+    let mut system = match unprefixed {
+        System::Decimal => System::Decimal,
+        System::Binary | System::Jedec => System::Jedec,
+    };
+    if magnitude.is_some() {
+        pos += 1;
+        if pos < bytes.len() && bytes[pos].eq_ignore_ascii_case(&b'i') {
+            system = System::Binary;
+            pos += 1;
+        }
+    }
+    // An upper-case B means bytes, a lower-case one bits, as in "MB" and "Mb".
+    let mut bits = false;
+    if pos < bytes.len() && bytes[pos] == b'B' {
+        pos += 1;
+    } else if starts_with(bytes, pos, b"bits") {
+        bits = true;
+        pos += "bits".len();
+    } else if starts_with(bytes, pos, b"bit") {
+        bits = true;
+        pos += "bit".len();
+    } else if starts_with(bytes, pos, b"b") {
+        bits = true;
+        pos += "b".len();
+    } else if magnitude.is_none() && skip_whitespace(bytes, pos) == pos && pos < bytes.len() {
+        return None;
+    }
+
+    Some((system, magnitude, bits, pos))
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn units() {
+        for system in enum_iterator::all::<System>() {
+            for magnitude in enum_iterator::all::<Magnitude>() {
+                let formatter = ByteSizeFormatter::new(system, magnitude);
+                let input = format!("3 {}", formatter.get_unit());
+                assert_eq!(
+                    Ok(3 * formatter.get_divisor_u128()),
+                    parse_size_u128_in(&input, system),
+                    "{input}"
+                );
+            }
+        }
+        assert_eq!(Ok(1024), parse_size_in("1 KB", System::Jedec));
+        assert_eq!(Ok(1024), parse_size_in("1 KB", System::Binary));
+        assert_eq!(Ok(1024), parse_size_in("1 KiB", System::Jedec));
+        assert_eq!(Ok(1000), parse_size_in("1 KB", System::Decimal));
+        assert_eq!(Ok(42), parse_size("42"));
+        assert_eq!(Ok(42), parse_size(" 42 B "));
+        assert_eq!(Ok(2 * 1024), parse_size("2Ki"));
+        assert_eq!(Ok(2048 * 1024), parse_size("2048 KiB"));
+        assert_eq!(Ok(7_000_000), parse_size("7m"));
+        assert_eq!(Ok(1_500_000), parse_size("1.50\u{202F}MB"));
+    }
+
+    #[test]
+    fn fractions() {
+        assert_eq!(Ok(1_500_000), parse_size("1.5MB"));
+        assert_eq!(Ok(1536), parse_size("1.5 KiB"));
+        assert_eq!(Ok(1_499_464), parse_size("1.43 MiB"));
+        assert_eq!(Ok(512), parse_size(".5 KiB"));
+        // 0.5 bytes rounds to even, 1.5 bytes rounds up to even.
+        assert_eq!(Ok(0), parse_size("0.5"));
+        assert_eq!(Ok(2), parse_size("1.5"));
+
+        // Long fractions only overflow when the size itself does.
+        assert_eq!(
+            Ok(123_456_789_012_345_679),
+            parse_size("0.1234567890123456789012 EB")
+        );
+        let fraction = format!("0.{}1 B", "0".repeat(36));
+        assert_eq!(Ok(0), parse_size(&fraction));
+        assert_eq!(
+            Ok(u64::MAX),
+            parse_size("18446744073709551615.4999999999999999999")
+        );
+        assert_eq!(
+            Err(ParseError::new(ParseErrorKind::Overflow, 0)),
+            parse_size("18446744073709551615.5")
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let precise = crate::options::FormatOptions::new().precision(20);
+        for value in [
+            u64::MAX,
+            u64::MAX / 3,
+            123_456_789_012_345_678,
+            1_500_000,
+            1023,
+        ] {
+            for system in enum_iterator::all::<System>() {
+                let formatted = ByteSizeFormatter::format_auto_with(value, system, precise);
+                assert_eq!(Ok(value), parse_size_in(&formatted, system), "{formatted}");
+            }
+        }
+        assert_eq!(Ok(u64::MAX), parse_size("15.99999999999999999913 EiB"));
+    }
+
+    #[test]
+    fn bits() {
+        for input in ["1Mb", "10mb", "1 kb", "1 Mbit", "1 Kibit", "12 bits", "4b"] {
+            assert_eq!(
+                Err(ParseErrorKind::UnknownUnit),
+                parse_size(input).map_err(|err| err.kind()),
+                "{input}"
+            );
+        }
+        assert_eq!(
+            Err(ParseError::new(ParseErrorKind::UnknownUnit, 3)),
+            parse_size("10 mb")
+        );
+        assert_eq!(Ok(1_000_000), parse_size("1MB"));
+        assert_eq!(Ok(1_000_000), parse_bits("1 Mb"));
+        assert_eq!(Ok(1_000_000), parse_bits("1 Mbit"));
+        assert_eq!(Ok(1024), parse_bits("1 Kibit"));
+        assert_eq!(Ok(12), parse_bits("12 bits"));
+        assert_eq!(Ok(8_000_000), parse_bits("1 MB"));
+        assert_eq!(Ok(80), parse_bits("10"));
+        assert_eq!(Ok(1_500_000_000), parse_bits("1.5 Gbit"));
+        assert_eq!(Ok(1), parse_bits("0.125 B"));
+    }
+
+    #[test]
+    fn infix_case() {
+        assert_eq!(Ok(1 << 30), parse_size("1 GIB"));
+        assert_eq!(Ok(1024), parse_size("1 KIB"));
+        assert_eq!(Ok(1024), parse_size("1 kiB"));
+        assert_eq!(Ok(1024), parse_bits("1 kib"));
+        assert_eq!(Ok(1024), parse_bits("1 KIbit"));
+    }
+
+    #[test]
+    fn constant() {
+        const LIMIT: Result<u64, ParseError> = parse_size("1.5 GiB");
+        const BITS: Result<u64, ParseError> = parse_bits("100 Mbit");
+        assert_eq!(Ok(1_610_612_736), LIMIT);
+        assert_eq!(Ok(100_000_000), BITS);
+        assert_eq!(1 << 30, crate::bytes!("1 GiB"));
+        assert_eq!(512, crate::bytes!(" 0.5 K ", System::Binary));
+        assert_eq!(u64::MAX, crate::bytes!("18446744073709551615"));
+    }
+
+    #[test]
+    fn errors() {
+        let error = |input| parse_size(input).map_err(|e| (e.kind(), e.position()));
+        assert_eq!(Err((ParseErrorKind::Empty, 0)), error(""));
+        assert_eq!(Err((ParseErrorKind::Empty, 2)), error("  "));
+        assert_eq!(Err((ParseErrorKind::InvalidNumber, 0)), error("MiB"));
+        assert_eq!(Err((ParseErrorKind::InvalidNumber, 2)), error("1. MB"));
+        assert_eq!(Err((ParseErrorKind::UnknownUnit, 2)), error("1 XB"));
+        assert_eq!(Err((ParseErrorKind::UnknownUnit, 1)), error("1MiBs"));
+        assert_eq!(Err((ParseErrorKind::UnknownUnit, 1)), error("1Mbyte"));
+        assert_eq!(Err((ParseErrorKind::Overflow, 0)), error("16 EiB"));
+        assert_eq!(
+            Err((ParseErrorKind::Overflow, 1)),
+            error(" 18446744073709551616")
+        );
+        assert_eq!(Ok(u64::MAX), parse_size("18446744073709551615"));
+        assert_eq!(Err((ParseErrorKind::Overflow, 0)), error("1 ZB"));
+        assert_eq!(Ok(10_u128.pow(21)), parse_size_u128("1 ZB"));
+        assert_eq!(
+            Err(ParseError::new(ParseErrorKind::Overflow, 0)),
+            parse_size_u128("300000000 QiB")
+        );
+    }
+}
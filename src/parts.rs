@@ -0,0 +1,178 @@
+use core::fmt;
+
+use crate::consts::{Magnitude, System};
+use crate::mantissa::Mantissa;
+use crate::options::FormatOptions;
+
+/// The pieces of a formatted value, for renderers that lay them out themselves, e.g. to
+/// emphasize the number but not the unit, or to emit structured data. Returned by
+/// [`ByteSizeFormatter::parts`](crate::ByteSizeFormatter::parts) and its variants, and
+/// displayed exactly like the corresponding `format_*` output.
+///
+/// # Example
+/// ```
+/// use bittenhumans::ByteSizeFormatter;
+/// use bittenhumans::consts::{Magnitude, System};
+///
+/// let formatter = ByteSizeFormatter::new(System::Binary, Magnitude::Mega);
+/// let parts = formatter.parts(1_500_000);
+/// assert_eq!(1, parts.get_integer());
+/// assert_eq!(&[4, 3], parts.get_fraction());
+/// assert_eq!("MiB", parts.get_unit());
+/// assert!(!parts.is_exact());
+///
+/// let mut html = String::new();
+/// html.push_str("<b>");
+/// parts.write_number(&mut html).unwrap();
+/// html.push_str("</b>");
+/// html.push_str(parts.get_separator());
+/// html.push_str(parts.get_unit());
+/// assert_eq!("<b>1.43</b> MiB", html);
+/// assert_eq!(formatter.format_value(1_500_000), parts.to_string());
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SizeParts {
+    sign: &'static str,
+    mantissa: Mantissa,
+    trim_trailing_zeros: bool,
+    decimal_point: char,
+    separator: &'static str,
+    system: System,
+    magnitude: Magnitude,
+    unit: &'static str,
+}
+
+impl SizeParts {
+    pub(crate) fn new(
+        sign: &'static str,
+        mantissa: Mantissa,
+        system: System,
+        magnitude: Magnitude,
+        unit: &'static str,
+        options: &FormatOptions,
+    ) -> Self {
+        Self {
+            sign,
+            mantissa,
+            trim_trailing_zeros: options.get_trim_trailing_zeros(),
+            decimal_point: options.get_locale().get_decimal_point(),
+            separator: options.get_separator().as_str(),
+            system,
+            magnitude,
+            unit,
+        }
+    }
+
+    /// The sign to show before the number, following the configured
+    /// [`SignPolicy`](crate::options::SignPolicy) and minus sign. Empty for unsigned values and
+    /// values that display as zero.
+    pub fn get_sign(&self) -> &'static str {
+        self.sign
+    }
+
+    /// The digits before the decimal point.
+    pub fn get_integer(&self) -> u128 {
+        self.mantissa.integer
+    }
+
+    /// The digits after the decimal point as values from 0 to 9, without trailing zeros if the
+    /// formatter trims them.
+    pub fn get_fraction(&self) -> &[u8] {
+        self.mantissa.shown_fraction(self.trim_trailing_zeros)
+    }
+
+    pub fn get_decimal_point(&self) -> char {
+        self.decimal_point
+    }
+
+    pub fn get_separator(&self) -> &'static str {
+        self.separator
+    }
+
+    pub fn get_system(&self) -> System {
+        self.system
+    }
+
+    pub fn get_magnitude(&self) -> Magnitude {
+        self.magnitude
+    }
+
+    /// The unit label in the configured locale, e.g. `"MiB"`, `"Mbit"` or `"mebibytes"`.
+    pub fn get_unit(&self) -> &'static str {
+        self.unit
+    }
+
+    /// Whether the number shows the value without rounding.
+    pub fn is_exact(&self) -> bool {
+        self.mantissa.exact
+    }
+
+    /// Writes the sign and the number, e.g. `"-1.43"`, without the separator and unit.
+    pub fn write_number(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_str(self.sign)?;
+        self.mantissa
+            .write_to(f, self.trim_trailing_zeros, self.decimal_point)
+    }
+}
+
+/// Shows the whole value, e.g. `"-1.43 MiB"`.
+impl fmt::Display for SizeParts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_number(f)?;
+        f.write_str(self.separator)?;
+        f.write_str(self.unit)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::consts::{Magnitude, System};
+    use crate::options::{FormatOptions, MinusSign, SignPolicy};
+    use crate::ByteSizeFormatter;
+
+    #[test]
+    fn parts() {
+        let formatter = ByteSizeFormatter::new(System::Decimal, Magnitude::Kilo);
+        let parts = formatter.parts(1500);
+        assert_eq!(
+            (1, &[5, 0][..], "KB"),
+            (parts.get_integer(), parts.get_fraction(), parts.get_unit())
+        );
+        assert_eq!(
+            (System::Decimal, Magnitude::Kilo),
+            (parts.get_system(), parts.get_magnitude())
+        );
+        assert_eq!(("", " "), (parts.get_sign(), parts.get_separator()));
+        assert!(parts.is_exact());
+        assert!(!formatter.parts(1501).is_exact());
+        assert_eq!(1234, formatter.parts_u128(1_234_000).get_integer());
+        assert_eq!("1.00 KB", formatter.parts_bits(8000).to_string());
+
+        let options = FormatOptions::new()
+            .sign(SignPolicy::Always)
+            .minus_sign(MinusSign::Unicode)
+            .trim_trailing_zeros(true);
+        let formatter = formatter.with_options(options);
+        assert_eq!("\u{2212}", formatter.parts_i64(-1500).get_sign());
+        assert_eq!(&[5], formatter.parts_i128(-1500).get_fraction());
+        assert_eq!("+", formatter.parts_i64(1500).get_sign());
+        assert_eq!("", formatter.parts_i64(0).get_sign());
+        assert_eq!("", formatter.parts_i64(-1).get_sign());
+        assert_eq!(
+            Some("+1.5 KB".to_string()),
+            formatter.parts_f64(1500.0).map(|parts| parts.to_string())
+        );
+        assert_eq!(None, formatter.parts_f64(f64::NAN));
+        assert_eq!(None, formatter.parts_f64(f64::NEG_INFINITY));
+
+        for value in [0, 999, 1000, 1_500_000, u64::MAX] {
+            for system in enum_iterator::all::<System>() {
+                let formatter = ByteSizeFormatter::fit(value, system);
+                assert_eq!(
+                    formatter.format_value(value),
+                    formatter.parts(value).to_string()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use alloc::format;
+use alloc::string::String;
+use core::time::Duration;
+
+use crate::consts::System;
+use crate::mantissa::Fraction;
+use crate::options::FormatOptions;
+use crate::rate::RateFormatter;
+use crate::{ByteSizeFormatter, Sign};
+
+/// Selects which parts of a progress summary are shown. Parts that cannot be computed, such as
+/// the percentage of an unknown total, are left out regardless.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ProgressTemplate {
+    sizes: bool,
+    percent: bool,
+    rate: bool,
+    eta: bool,
+}
+
+impl Default for ProgressTemplate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressTemplate {
+    /// Creates a template showing every part.
+    pub const fn new() -> Self {
+        Self {
+            sizes: true,
+            percent: true,
+            rate: true,
+            eta: true,
+        }
+    }
+
+    /// Shows the transferred and total size, e.g. `"1.20 / 4.00 GiB"`.
+    pub const fn sizes(mut self, show: bool) -> Self {
+        self.sizes = show;
+        self
+    }
+
+    /// Shows the completed percentage, e.g. `"(30%)"`.
+    pub const fn percent(mut self, show: bool) -> Self {
+        self.percent = show;
+        self
+    }
+
+    /// Shows the transfer rate, e.g. `"12.3 MiB/s"`.
+    pub const fn rate(mut self, show: bool) -> Self {
+        self.rate = show;
+        self
+    }
+
+    /// Shows the estimated time remaining, e.g. `"ETA 3m 52s"`.
+    pub const fn eta(mut self, show: bool) -> Self {
+        self.eta = show;
+        self
+    }
+
+    pub fn get_sizes(&self) -> bool {
+        self.sizes
+    }
+
+    pub fn get_percent(&self) -> bool {
+        self.percent
+    }
+
+    pub fn get_rate(&self) -> bool {
+        self.rate
+    }
+
+    pub fn get_eta(&self) -> bool {
+        self.eta
+    }
+}
+
+/// Formats transfer progress such as `"1.20 / 4.00 GiB (30%) — 12.3 MiB/s, ETA 3m 54s"`.
+///
+/// Both sizes share the unit chosen by [`ByteSizeFormatter::fit_with`] for the total. When the
+/// total is unknown, only the transferred size and the rate are shown.
+///
+/// # Example
+/// ```
+/// use bittenhumans::consts::System;
+/// use bittenhumans::progress::{ProgressFormatter, ProgressTemplate};
+///
+/// let progress = ProgressFormatter::new(System::Binary);
+/// let rate = Some(12.3 * 1024.0 * 1024.0);
+/// assert_eq!(
+///     "1.20 / 4.00 GiB (30%) — 12.3 MiB/s, ETA 3m 54s",
+///     progress.format(1_288_490_189, Some(4 << 30), rate)
+/// );
+/// assert_eq!("1.20 GiB — 12.3 MiB/s", progress.format(1_288_490_189, None, rate));
+///
+/// let compact = progress.with_template(ProgressTemplate::new().sizes(false).rate(false));
+/// assert_eq!("30% — ETA 3m 54s", compact.format(1_288_490_189, Some(4 << 30), rate));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ProgressFormatter {
+    system: System,
+    options: FormatOptions,
+    rate: RateFormatter,
+    template: ProgressTemplate,
+}
+
+impl ProgressFormatter {
+    /// Creates a progress formatter showing every part, with sizes in the default options and
+    /// rates per second with three significant figures.
+    pub fn new(system: System) -> Self {
+        let rate_options = FormatOptions::new().significant_figures(3);
+        Self {
+            system,
+            options: FormatOptions::new(),
+            rate: RateFormatter::new(system).with_options(rate_options),
+            template: ProgressTemplate::new(),
+        }
+    }
+
+    /// Replaces the options used to format the sizes.
+    pub fn with_options(mut self, options: FormatOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Replaces the formatter used for the rate.
+    pub fn with_rate_formatter(mut self, rate: RateFormatter) -> Self {
+        self.rate = rate;
+        self
+    }
+
+    /// Selects which parts are shown.
+    pub fn with_template(mut self, template: ProgressTemplate) -> Self {
+        self.template = template;
+        self
+    }
+
+    pub fn get_system(&self) -> System {
+        self.system
+    }
+
+    pub fn get_options(&self) -> &FormatOptions {
+        &self.options
+    }
+
+    pub fn get_rate_formatter(&self) -> &RateFormatter {
+        &self.rate
+    }
+
+    pub fn get_template(&self) -> ProgressTemplate {
+        self.template
+    }
+
+    /// Formats the progress of a transfer of `done` out of `total` bytes, at `rate` bytes per
+    /// second, e.g. from [`RateMeter::rate`](crate::meter::RateMeter::rate).
+    ///
+    /// # Arguments
+    /// * `done` - The number of bytes transferred so far.
+    /// * `total` - The size of the transfer, if known.
+    /// * `rate` - The current rate in bytes per second, if known. A NaN rate is treated as
+    ///   unknown, and the ETA is only shown for positive rates.
+    pub fn format(&self, done: u64, total: Option<u64>, rate: Option<f64>) -> String {
+        let mut output = String::new();
+        if self.template.sizes {
+            output.push_str(&self.format_sizes(done, total));
+        }
+        if let (true, Some(total)) = (self.template.percent, total) {
+            let percent = match total {
+                0 => 100,
+                _ => done as u128 * 100 / total as u128,
+            };
+            match output.is_empty() {
+                true => output.push_str(&format!("{percent}%")),
+                false => output.push_str(&format!(" ({percent}%)")),
+            }
+        }
+
+        let rate = rate.filter(|rate| !rate.is_nan());
+        let mut details = String::new();
+        if let (true, Some(rate)) = (self.template.rate, rate) {
+            details.push_str(&self.rate.format_per_second(rate));
+        }
+        if let (true, Some(eta)) = (self.template.eta, Self::eta(done, total, rate)) {
+            if !details.is_empty() {
+                details.push_str(", ");
+            }
+            details.push_str("ETA ");
+            details.push_str(&format_eta(eta));
+        }
+
+        if !details.is_empty() {
+            if !output.is_empty() {
+                output.push_str(" — ");
+            }
+            output.push_str(&details);
+        }
+        output
+    }
+
+    fn format_sizes(&self, done: u64, total: Option<u64>) -> String {
+        let Some(total) = total else {
+            return ByteSizeFormatter::format_auto_with(done, self.system, self.options);
+        };
+
+        let formatter = ByteSizeFormatter::fit_with(total, self.system, self.options);
+        let done = Fraction::integer(done as u128);
+        let mut output = String::new();
+        formatter
+            .parts_abs(done, Sign::Unsigned)
+            .write_number(&mut output)
+            .expect("writing to a String cannot fail");
+        output.push_str(" / ");
+        output.push_str(&formatter.format_value(total));
+        output
+    }
+
+    /// The time remaining at the given rate, if it can be estimated.
+    fn eta(done: u64, total: Option<u64>, rate: Option<f64>) -> Option<Duration> {
+        let remaining = total?.saturating_sub(done);
+        match rate {
+            _ if remaining == 0 => Some(Duration::ZERO),
+            Some(rate) if rate > 0.0 => Duration::try_from_secs_f64(remaining as f64 / rate).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// Formats a remaining time with its two largest units, rounded up to whole seconds: `"52s"`,
+/// `"3m 54s"`, `"2h 5m"`, `"3d 4h"`.
+pub fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs() + (eta.subsec_nanos() > 0) as u64;
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m {}s", secs / 60, secs % 60),
+        3600..86400 => format!("{}h {}m", secs / 3600, secs % 3600 / 60),
+        _ => format!("{}d {}h", secs / 86400, secs % 86400 / 3600),
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format() {
+        let progress = ProgressFormatter::new(System::Decimal);
+        assert_eq!(
+            "0.00 / 4.00 GB (0%)",
+            progress.format(0, Some(4_000_000_000), None)
+        );
+        assert_eq!(
+            "0.50 / 1.00 MB (50%) — 100 KB/s, ETA 5s",
+            progress.format(500_000, Some(1_000_000), Some(100_000.0))
+        );
+        assert_eq!(
+            "998 / 999 B (99%) — 0 B/s",
+            progress.format(998, Some(999), Some(0.0))
+        );
+        assert_eq!(
+            "999 / 999 B (100%) — ETA 0s",
+            progress.format(999, Some(999), None)
+        );
+        assert_eq!("0 / 0 B (100%) — ETA 0s", progress.format(0, Some(0), None));
+        assert_eq!("1.50 GB", progress.format(1_500_000_000, None, None));
+
+        let sizes = ProgressTemplate::new()
+            .percent(false)
+            .rate(false)
+            .eta(false);
+        let trimmed = progress
+            .with_options(FormatOptions::new().trim_trailing_zeros(true))
+            .with_template(sizes);
+        assert_eq!(
+            "1.2 / 4 GB",
+            trimmed.format(1_200_000_000, Some(4_000_000_000), None)
+        );
+        assert_eq!(
+            "",
+            progress
+                .with_template(sizes.sizes(false))
+                .format(1, Some(2), None)
+        );
+    }
+
+    #[test]
+    fn eta() {
+        assert_eq!("0s", format_eta(Duration::ZERO));
+        assert_eq!("1s", format_eta(Duration::from_millis(1)));
+        assert_eq!("59s", format_eta(Duration::from_secs(59)));
+        assert_eq!("1m 0s", format_eta(Duration::from_secs(60)));
+        assert_eq!("2h 5m", format_eta(Duration::from_secs(7500)));
+        assert_eq!(
+            "3d 4h",
+            format_eta(Duration::from_secs(3 * 86400 + 4 * 3600 + 59))
+        );
+
+        let progress = ProgressFormatter::new(System::Binary);
+        let eta = |done, total, rate| progress.format(done, total, rate);
+        assert_eq!("1.00 KiB — 10 B/s", eta(1024, None, Some(10.0)));
+        assert_eq!("0 / 10 B (0%)", eta(0, Some(10), Some(f64::NAN)));
+        assert_eq!("0 B", eta(0, None, Some(f64::NAN)));
+        assert_eq!(
+            "0.00 / 16.00 EiB (0%) — 0 B/s",
+            eta(0, Some(u64::MAX), Some(1e-20))
+        );
+    }
+}
@@ -0,0 +1,200 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::fmt;
+use core::time::Duration;
+
+use crate::consts::System;
+use crate::mantissa::Fraction;
+use crate::options::FormatOptions;
+use crate::{ByteSizeFormatter, Sign};
+
+/// The time unit a rate is expressed per.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum TimeUnit {
+    #[default]
+    Second,
+    Minute,
+    Hour,
+}
+
+impl TimeUnit {
+    /// The suffix appended to the unit, e.g. `"/s"`.
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            TimeUnit::Second => "/s",
+            TimeUnit::Minute => "/min",
+            TimeUnit::Hour => "/h",
+        }
+    }
+
+    pub fn as_secs(&self) -> u64 {
+        match self {
+            TimeUnit::Second => 1,
+            TimeUnit::Minute => 60,
+            TimeUnit::Hour => 60 * 60,
+        }
+    }
+}
+
+/// Formats data rates such as `"12.30 MiB/s"` or `"98.40 Mbit/s"`, picking the magnitude for each
+/// rate like [`ByteSizeFormatter::format_auto`] does for sizes.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+///
+/// use bittenhumans::consts::System;
+/// use bittenhumans::options::{BitSymbol, FormatOptions, Units};
+/// use bittenhumans::rate::{RateFormatter, TimeUnit};
+///
+/// let disk = RateFormatter::new(System::Binary);
+/// assert_eq!("12.30 MiB/s", disk.format(12_897_485, Duration::from_secs(1)));
+/// assert_eq!("512 B/s", disk.format(1024, Duration::from_secs(2)));
+///
+/// let link = RateFormatter::new(System::Decimal)
+///     .with_options(FormatOptions::new().precision(1).units(Units::Bits(BitSymbol::Long)));
+/// assert_eq!("98.4 Mbit/s", link.format(123_000_000, Duration::from_secs(10)));
+///
+/// let quota = RateFormatter::new(System::Decimal).with_time_unit(TimeUnit::Hour);
+/// assert_eq!("3.60 GB/h", quota.format_per_second(1_000_000.0));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RateFormatter {
+    system: System,
+    options: FormatOptions,
+    time_unit: TimeUnit,
+}
+
+impl RateFormatter {
+    /// Creates a rate formatter for the given numeral system, with default options and rates per
+    /// second.
+    pub fn new(system: System) -> Self {
+        Self {
+            system,
+            options: FormatOptions::new(),
+            time_unit: TimeUnit::Second,
+        }
+    }
+
+    /// Replaces the options used to format the quantity.
+    pub fn with_options(mut self, options: FormatOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets the time unit rates are expressed per.
+    pub fn with_time_unit(mut self, time_unit: TimeUnit) -> Self {
+        self.time_unit = time_unit;
+        self
+    }
+
+    pub fn get_system(&self) -> System {
+        self.system
+    }
+
+    pub fn get_options(&self) -> &FormatOptions {
+        &self.options
+    }
+
+    pub fn get_time_unit(&self) -> TimeUnit {
+        self.time_unit
+    }
+
+    /// Formats the rate at which `bytes` were transferred during `elapsed`. The rate is computed
+    /// exactly. A zero duration yields an infinite rate, or NaN if no bytes were transferred,
+    /// shown as in [`ByteSizeFormatter::format_f64`].
+    #[cfg(feature = "alloc")]
+    pub fn format(&self, bytes: u64, elapsed: Duration) -> String {
+        crate::collect(|output| self.write_to(output, bytes, elapsed))
+    }
+
+    /// Formats a rate given in bytes per second, converted to the configured time unit. Negative
+    /// and non-finite rates follow [`ByteSizeFormatter::format_f64`].
+    #[cfg(feature = "alloc")]
+    pub fn format_per_second(&self, bytes_per_second: f64) -> String {
+        crate::collect(|output| self.write_per_second(output, bytes_per_second))
+    }
+
+    /// Like [`format`](Self::format), but writes into `f` without allocating.
+    pub fn write_to(&self, f: &mut impl fmt::Write, bytes: u64, elapsed: Duration) -> fmt::Result {
+        if elapsed.is_zero() {
+            let rate = if bytes == 0 { f64::NAN } else { f64::INFINITY };
+            return self.write_per_second(f, rate);
+        }
+
+        let unit_nanos = self.time_unit.as_secs() as u128 * 1_000_000_000;
+        let rate = Fraction {
+            numerator: bytes as u128 * unit_nanos,
+            denominator: elapsed.as_nanos(),
+        };
+        let formatter = ByteSizeFormatter::fit_abs(rate, Sign::Unsigned, self.system, self.options);
+        formatter.write_abs(f, rate, Sign::Unsigned)?;
+        f.write_str(self.time_unit.suffix())
+    }
+
+    /// Like [`format_per_second`](Self::format_per_second), but writes into `f` without
+    /// allocating.
+    pub fn write_per_second(&self, f: &mut impl fmt::Write, bytes_per_second: f64) -> fmt::Result {
+        let rate = bytes_per_second * self.time_unit.as_secs() as f64;
+        ByteSizeFormatter::fit_f64_with(rate, self.system, self.options).write_f64(f, rate)?;
+        f.write_str(self.time_unit.suffix())
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::options::{BitSymbol, Units};
+
+    #[test]
+    fn format() {
+        let rate = RateFormatter::new(System::Binary);
+        assert_eq!("0 B/s", rate.format(0, Duration::from_secs(1)));
+        assert_eq!("1023 B/s", rate.format(1023, Duration::from_secs(1)));
+        assert_eq!("1.00 MiB/s", rate.format(1_048_575, Duration::from_secs(1)));
+        assert_eq!("3.33 KiB/s", rate.format(10 * 1024, Duration::from_secs(3)));
+        assert_eq!("inf QiB/s", rate.format(1, Duration::ZERO));
+        assert_eq!("NaN B/s", rate.format(0, Duration::ZERO));
+        assert_eq!(
+            "15.62 EiB/s",
+            rate.format(u64::MAX, Duration::from_nanos(1_024_000_000))
+        );
+
+        let per_minute = rate.with_time_unit(TimeUnit::Minute);
+        assert_eq!(
+            "60.00 KiB/min",
+            per_minute.format(1024, Duration::from_secs(1))
+        );
+        assert_eq!("1 B/min", per_minute.format(1, Duration::from_secs(60)));
+        let per_hour = rate.with_time_unit(TimeUnit::Hour);
+        assert_eq!("3.52 KiB/h", per_hour.format(1, Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn bits() {
+        let short = FormatOptions::new().units(Units::Bits(BitSymbol::Short));
+        let rate = RateFormatter::new(System::Decimal).with_options(short);
+        assert_eq!("8 b/s", rate.format(1, Duration::from_secs(1)));
+        assert_eq!(
+            "1.00 Gb/s",
+            rate.format(125_000_000, Duration::from_secs(1))
+        );
+        assert_eq!("100.00 Mb/s", rate.format_per_second(12_500_000.0));
+    }
+
+    #[test]
+    fn per_second() {
+        let rate = RateFormatter::new(System::Binary);
+        assert_eq!(
+            "12.30 MiB/s",
+            rate.format_per_second(12.3 * 1024.0 * 1024.0)
+        );
+        assert_eq!("0 B/s", rate.format_per_second(0.2));
+        assert_eq!("-1.00 KiB/s", rate.format_per_second(-1024.0));
+        assert_eq!(
+            "60.00 KiB/min",
+            rate.with_time_unit(TimeUnit::Minute)
+                .format_per_second(1024.0)
+        );
+    }
+}
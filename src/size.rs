@@ -0,0 +1,378 @@
+use core::fmt;
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use core::str::FromStr;
+
+use crate::consts::{Magnitude, System};
+use crate::convert::{convert, Ratio, Unit};
+use crate::display::HumanBytes;
+use crate::parse::{parse_size, ParseError};
+use crate::ByteSizeFormatter;
+
+/// A number of bytes.
+///
+/// Arithmetic behaves like it does on `u64`, so overflow panics in debug builds; use the
+/// `checked_*` and `saturating_*` methods where that matters. Sizes are displayed in the
+/// [`Decimal`](System::Decimal) system like [`HumanBytes`], honouring the same formatting flags,
+/// so `{:#}` shows them in the [`Binary`](System::Binary) system. Parsing follows
+/// [`parse_size`].
+///
+/// # Example
+/// ```
+/// use bittenhumans::ByteSize;
+///
+/// let total: ByteSize = [ByteSize::mib(512), ByteSize::kib(256)].into_iter().sum();
+/// assert_eq!(ByteSize::kib(512 * 1024 + 256), total);
+/// assert_eq!("512.25 MiB", format!("{total:#}"));
+/// assert_eq!("537.1 MB", format!("{total:.1}"));
+///
+/// let limit: ByteSize = "1 GiB".parse().unwrap();
+/// assert!(total < limit);
+/// assert_eq!(Some(ByteSize::mib(511) + ByteSize::kib(768)), limit.checked_sub(total));
+/// assert_eq!(ByteSize::b(0), total.saturating_sub(limit));
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+pub struct ByteSize(pub u64);
+
+/// Scales `value` by the divisor of a magnitude, panicking if the size does not fit.
+const fn scaled(value: u64, system: System, magnitude: Magnitude) -> ByteSize {
+    match ByteSize::from_unit(value, system, magnitude) {
+        Some(size) => size,
+        None => panic!("byte size overflows a u64"),
+    }
+}
+
+impl ByteSize {
+    /// Creates a size of `value` units of the given magnitude, or `None` if it exceeds
+    /// `u64::MAX` bytes.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSize;
+    /// use bittenhumans::consts::{Magnitude, System};
+    ///
+    /// assert_eq!(
+    ///     Some(ByteSize(16 << 30)),
+    ///     ByteSize::from_unit(16, System::Jedec, Magnitude::Giga)
+    /// );
+    /// assert_eq!(None, ByteSize::from_unit(1, System::Decimal, Magnitude::Zetta));
+    /// ```
+    pub const fn from_unit(value: u64, system: System, magnitude: Magnitude) -> Option<Self> {
+        match (value as u128).checked_mul(ByteSizeFormatter::compute_divisor(system, magnitude)) {
+            Some(bytes) if bytes <= u64::MAX as u128 => Some(Self(bytes as u64)),
+            _ => None,
+        }
+    }
+
+    pub const fn b(value: u64) -> Self {
+        Self(value)
+    }
+
+    pub const fn kb(value: u64) -> Self {
+        scaled(value, System::Decimal, Magnitude::Kilo)
+    }
+
+    pub const fn mb(value: u64) -> Self {
+        scaled(value, System::Decimal, Magnitude::Mega)
+    }
+
+    pub const fn gb(value: u64) -> Self {
+        scaled(value, System::Decimal, Magnitude::Giga)
+    }
+
+    pub const fn tb(value: u64) -> Self {
+        scaled(value, System::Decimal, Magnitude::Tera)
+    }
+
+    pub const fn pb(value: u64) -> Self {
+        scaled(value, System::Decimal, Magnitude::Peta)
+    }
+
+    pub const fn eb(value: u64) -> Self {
+        scaled(value, System::Decimal, Magnitude::Exa)
+    }
+
+    pub const fn kib(value: u64) -> Self {
+        scaled(value, System::Binary, Magnitude::Kilo)
+    }
+
+    pub const fn mib(value: u64) -> Self {
+        scaled(value, System::Binary, Magnitude::Mega)
+    }
+
+    pub const fn gib(value: u64) -> Self {
+        scaled(value, System::Binary, Magnitude::Giga)
+    }
+
+    pub const fn tib(value: u64) -> Self {
+        scaled(value, System::Binary, Magnitude::Tera)
+    }
+
+    pub const fn pib(value: u64) -> Self {
+        scaled(value, System::Binary, Magnitude::Peta)
+    }
+
+    pub const fn eib(value: u64) -> Self {
+        scaled(value, System::Binary, Magnitude::Exa)
+    }
+
+    /// The number of bytes.
+    pub const fn as_u64(&self) -> u64 {
+        self.0
+    }
+
+    /// The exact number of `unit`s in this size.
+    ///
+    /// # Example
+    /// ```
+    /// use bittenhumans::ByteSize;
+    /// use bittenhumans::consts::{Magnitude, System};
+    /// use bittenhumans::convert::{Ratio, Unit};
+    ///
+    /// let mib = Unit::new(System::Binary, Magnitude::Mega);
+    /// assert_eq!(Ratio::new(3, 2), ByteSize::kib(1536).in_unit(mib));
+    /// ```
+    pub const fn in_unit(&self, unit: Unit) -> Ratio {
+        match convert(Ratio::integer(self.0 as u128), Unit::BYTE, unit) {
+            Some(ratio) => ratio,
+            // Both the byte count and every divisor fit into 101 bits.
+            None => unreachable!(),
+        }
+    }
+
+    /// Displays the size in the given numeral system.
+    pub const fn display(&self, system: System) -> HumanBytes {
+        HumanBytes(self.0, system)
+    }
+
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(bytes) => Some(Self(bytes)),
+            None => None,
+        }
+    }
+
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) {
+            Some(bytes) => Some(Self(bytes)),
+            None => None,
+        }
+    }
+
+    pub const fn checked_mul(self, rhs: u64) -> Option<Self> {
+        match self.0.checked_mul(rhs) {
+            Some(bytes) => Some(Self(bytes)),
+            None => None,
+        }
+    }
+
+    pub const fn checked_div(self, rhs: u64) -> Option<Self> {
+        match self.0.checked_div(rhs) {
+            Some(bytes) => Some(Self(bytes)),
+            None => None,
+        }
+    }
+
+    pub const fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    pub const fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    pub const fn saturating_mul(self, rhs: u64) -> Self {
+        Self(self.0.saturating_mul(rhs))
+    }
+}
+
+impl From<u64> for ByteSize {
+    fn from(bytes: u64) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<ByteSize> for u64 {
+    fn from(size: ByteSize) -> Self {
+        size.0
+    }
+}
+
+impl Add for ByteSize {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for ByteSize {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for ByteSize {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for ByteSize {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Mul<u64> for ByteSize {
+    type Output = Self;
+
+    fn mul(self, rhs: u64) -> Self {
+        Self(self.0 * rhs)
+    }
+}
+
+impl Mul<ByteSize> for u64 {
+    type Output = ByteSize;
+
+    fn mul(self, rhs: ByteSize) -> ByteSize {
+        ByteSize(self * rhs.0)
+    }
+}
+
+impl MulAssign<u64> for ByteSize {
+    fn mul_assign(&mut self, rhs: u64) {
+        self.0 *= rhs;
+    }
+}
+
+impl Div<u64> for ByteSize {
+    type Output = Self;
+
+    fn div(self, rhs: u64) -> Self {
+        Self(self.0 / rhs)
+    }
+}
+
+/// The number of times `rhs` fits into the size, e.g. how many pages a buffer spans.
+impl Div for ByteSize {
+    type Output = u64;
+
+    fn div(self, rhs: Self) -> u64 {
+        self.0 / rhs.0
+    }
+}
+
+impl DivAssign<u64> for ByteSize {
+    fn div_assign(&mut self, rhs: u64) {
+        self.0 /= rhs;
+    }
+}
+
+impl Sum for ByteSize {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self(0), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a ByteSize> for ByteSize {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&HumanBytes(self.0, System::Decimal), f)
+    }
+}
+
+impl FromStr for ByteSize {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        parse_size(s).map(Self)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::parse::ParseErrorKind;
+
+    #[test]
+    fn constructors() {
+        assert_eq!(ByteSize(1000), ByteSize::kb(1));
+        assert_eq!(ByteSize(1024), ByteSize::kib(1));
+        assert_eq!(ByteSize(3 << 20), ByteSize::mib(3));
+        assert_eq!(ByteSize(2_000_000_000_000), ByteSize::tb(2));
+        assert_eq!(ByteSize(15 << 60), ByteSize::eib(15));
+        assert_eq!(ByteSize(18 * 10_u64.pow(18)), ByteSize::eb(18));
+        assert_eq!(
+            None,
+            ByteSize::from_unit(16, System::Binary, Magnitude::Exa)
+        );
+        assert_eq!(
+            Some(ByteSize(u64::MAX)),
+            ByteSize::from_unit(u64::MAX, System::Decimal, Magnitude::Byte)
+        );
+        assert_eq!(5, u64::from(ByteSize::from(5)));
+    }
+
+    #[test]
+    #[should_panic(expected = "byte size overflows a u64")]
+    fn overflowing_constructor() {
+        ByteSize::eib(16);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut size = ByteSize::kib(4);
+        size += ByteSize::kib(4);
+        size -= ByteSize::b(1024);
+        size *= 3;
+        size /= 7;
+        assert_eq!(ByteSize::kib(3), size);
+        assert_eq!(ByteSize::kib(6), 2 * size);
+        assert_eq!(ByteSize::kib(6), size * 2);
+        assert_eq!(ByteSize::b(1536), size / 2);
+        assert_eq!(768, ByteSize::mib(3) / ByteSize::kib(4));
+
+        let max = ByteSize(u64::MAX);
+        assert_eq!(None, max.checked_add(ByteSize(1)));
+        assert_eq!(None, ByteSize(0).checked_sub(ByteSize(1)));
+        assert_eq!(None, max.checked_mul(2));
+        assert_eq!(None, max.checked_div(0));
+        assert_eq!(Some(ByteSize(2)), ByteSize(5).checked_div(2));
+        assert_eq!(max, max.saturating_add(ByteSize(1)));
+        assert_eq!(ByteSize(0), ByteSize(0).saturating_sub(ByteSize(1)));
+        assert_eq!(max, max.saturating_mul(2));
+
+        let sizes = [ByteSize::kb(1), ByteSize::kb(2), ByteSize::kb(3)];
+        assert_eq!(ByteSize::kb(6), sizes.iter().sum());
+        assert_eq!(ByteSize(0), Vec::<ByteSize>::new().into_iter().sum());
+        assert_eq!(Some(&ByteSize::kb(3)), sizes.iter().max());
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!("1.50 MB", ByteSize(1_500_000).to_string());
+        assert_eq!("1.43 MiB", format!("{:#}", ByteSize(1_500_000)));
+        assert_eq!("  1.5 MB", format!("{:>8.1}", ByteSize(1_500_000)));
+        assert_eq!(
+            "16.00 GB",
+            ByteSize::gib(16).display(System::Jedec).to_string()
+        );
+
+        assert_eq!(Ok(ByteSize::mib(512)), "512MiB".parse());
+        assert_eq!(
+            ParseErrorKind::UnknownUnit,
+            "1 XB".parse::<ByteSize>().unwrap_err().kind()
+        );
+        for size in [ByteSize(0), ByteSize::kb(999), ByteSize::mb(12)] {
+            assert_eq!(Ok(size), size.to_string().parse());
+        }
+    }
+}